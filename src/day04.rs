@@ -1,8 +1,8 @@
 use anyhow::{bail, Result, Error};
 use aoc_runner_derive::{aoc, aoc_generator};
-use itertools::Itertools;
 
-use crate::utils::grid::{Grid, Position};
+use crate::utils::cellular_automaton::{Automaton, UpdateMode};
+use crate::utils::grid::{Grid, Neighborhood, Position, ToChar};
 
 #[derive(Copy, Clone, PartialEq, Eq)]
 enum Tile {
@@ -22,6 +22,15 @@ impl TryFrom<char> for Tile {
     }
 }
 
+impl ToChar for Tile {
+    fn to_char(&self) -> char {
+        match self {
+            Tile::Floor => '.',
+            Tile::RollOfPaper => '@',
+        }
+    }
+}
+
 type Input = Grid<Tile>;
 
 #[aoc_generator(day4)]
@@ -29,17 +38,18 @@ fn parse(input: &str) -> Result<Input> {
     Grid::parse(input)
 }
 
+fn is_removable(grid: &Input, position: &Position) -> bool {
+    let count_neighboring_rolls = grid.neighbors(position, Neighborhood::Moore)
+        .filter(|(_, tile)| **tile == Tile::RollOfPaper)
+        .count();
+
+    count_neighboring_rolls < 4
+}
+
 fn list_removable_rolls(grid: &Input) -> impl IntoIterator<Item = Position> {
     grid.into_iter()
         .filter(|(_, tile)| **tile == Tile::RollOfPaper)
-        .filter(|(position, _)| {
-            let count_neighboring_rolls = position.neighboring_positions()
-                .into_iter()
-                .filter(|p| grid.get(p).copied() == Some(Tile::RollOfPaper))
-                .count();
-
-            count_neighboring_rolls < 4
-        })
+        .filter(|(position, _)| is_removable(grid, position))
         .map(|(p, _)| p)
 }
 
@@ -52,24 +62,16 @@ fn part1(grid: &Input) -> usize {
 
 #[aoc(day4, part2)]
 fn part2(grid: &Input) -> usize {
-    let mut grid = grid.clone();
-    let mut count = 0;
-
-    loop {
-        let removable = list_removable_rolls(&grid).into_iter().collect_vec();
-
-        if removable.is_empty() {
-            break;
+    Automaton::new(grid.clone(), |grid, position, tile| {
+        if *tile == Tile::RollOfPaper && is_removable(grid, position) {
+            Tile::Floor
+        } else {
+            *tile
         }
-
-        count += removable.len();
-
-        for p in removable {
-            grid.set(&p, Tile::Floor);
-        }
-    }
-
-    count
+    })
+        .with_mode(UpdateMode::Incremental)
+        .map(|removed| removed.len())
+        .sum()
 }
 
 #[cfg(test)]
@@ -2,7 +2,7 @@ use anyhow::{bail, Context, Error, Result};
 use aoc_runner_derive::{aoc, aoc_generator};
 use itertools::Itertools;
 
-use crate::utils::grid::{Grid, Position};
+use crate::utils::grid::{Grid, Position, ToChar};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Tile {
@@ -24,6 +24,16 @@ impl TryFrom<char> for Tile {
     }
 }
 
+impl ToChar for Tile {
+    fn to_char(&self) -> char {
+        match self {
+            Self::Space => '.',
+            Self::Source => 'S',
+            Self::Splitter => '^',
+        }
+    }
+}
+
 type Input = (Grid<Tile>, Position);
 
 #[aoc_generator(day7)]
@@ -1,10 +1,9 @@
-use std::collections::VecDeque;
-
 use anyhow::{bail, Context, Result};
 use aoc_runner_derive::{aoc, aoc_generator};
-use hashbrown::{HashMap, HashSet};
 use itertools::Itertools;
 
+use crate::utils::union_find::Kruskal;
+
 type Point = (isize, isize, isize);
 type Input = Vec<Point>;
 
@@ -26,85 +25,26 @@ fn parse(input: &str) -> Result<Input> {
         .try_collect()
 }
 
-fn normalize_pair(a: Point, b: Point) -> (Point, Point) {
-    if a <= b {
-        (a, b)
-    } else {
-        (b, a)
-    }
-}
-
-fn find_distances(points: &[Point]) -> HashMap<(Point, Point), f64> {
-    let mut distances: HashMap<(Point, Point), f64> = HashMap::new();
-
-    for (p1, p2) in points.iter().tuple_combinations() {
-        let distance = (((p1.0 - p2.0).pow(2) + (p1.1 - p2.1).pow(2) + (p1.2 - p2.2).pow(2)) as f64).sqrt();
-        let pair = normalize_pair(*p1, *p2);
-        distances.insert(pair, distance);
-    }
-
-    distances
+fn squared_distance(p1: &Point, p2: &Point) -> isize {
+    (p1.0 - p2.0).pow(2) + (p1.1 - p2.1).pow(2) + (p1.2 - p2.2).pow(2)
 }
 
-fn find_edges_by_distance(distances: &HashMap<(Point, Point), f64>) -> Vec<(Point, Point)> {
-    distances
-        .into_iter()
-        .sorted_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
-        .map(|((p1, p2), _)| (*p1, *p2))
+fn find_edges_by_distance(points: &[Point]) -> Vec<(usize, usize)> {
+    (0..points.len())
+        .tuple_combinations()
+        .sorted_by_cached_key(|&(i, j)| squared_distance(&points[i], &points[j]))
         .collect_vec()
 }
 
-fn find_circuits(
-    points: &[(isize, isize, isize)],
-    edges: &[(Point, Point)],
-) -> Vec<HashSet<(isize, isize, isize)>> {
-    let mut connections: HashMap<Point, Vec<Point>> = HashMap::new();
-
-    for &(p1, p2) in edges {
-        connections.entry(p1).or_default().push(p2);
-        connections.entry(p2).or_default().push(p1);
-    }
-
-    let mut circuits = vec![];
-    let mut processed: HashSet<Point> = HashSet::new();
-
-    for &p in points {
-        if processed.contains(&p) { continue; }
-
-        let mut circuit: HashSet<Point> = HashSet::from([p]);
-        let mut queue = VecDeque::from([p]);
-
-        while let Some(q) = queue.pop_front() {
-            let Some(rs) = connections.get(&q) else { continue; };
-
-            for &r in rs {
-                if !circuit.contains(&r) {
-                    queue.push_back(r);
-                    circuit.insert(r);
-                }
-            }
-        }
-
-        processed.extend(circuit.iter().copied());
-        circuits.push(circuit);
-    }
-
-    circuits
-}
-
 fn solve1(points: &Input, num_pairs: usize) -> usize {
-    let distances = find_distances(points);
+    let edges = find_edges_by_distance(points);
+    let mut merges = Kruskal::new(points.len(), edges.into_iter().take(num_pairs));
+    merges.by_ref().for_each(drop);
 
-    let edges = find_edges_by_distance(&distances)
+    merges
+        .union_find()
+        .component_sizes()
         .into_iter()
-        .take(num_pairs)
-        .collect_vec();
-
-    let circuits = find_circuits(points, &edges);
-
-    circuits
-        .into_iter()
-        .map(|circuit| circuit.len())
         .sorted()
         .rev()
         .take(3)
@@ -118,24 +58,12 @@ fn part1(input: &Input) -> usize {
 
 #[aoc(day8, part2)]
 fn part2(points: &Input) -> isize {
-    let distances = find_distances(points);
-    let edges = find_edges_by_distance(&distances);
-
-    let mut lower = 1;
-    let mut upper = edges.len();
-
-    while lower + 1 < upper {
-        let mid = lower + (upper - lower) / 2;
-
-        if find_circuits(points, &edges[0..mid]).len() == 1 {
-            upper = mid;
-        } else {
-            lower = mid;
-        }
-    }
+    let edges = find_edges_by_distance(points);
+    let last_merge = Kruskal::new(points.len(), edges)
+        .find(|merge| merge.components == 1)
+        .expect("Points never form a single circuit");
 
-    let (p1, p2) = edges[lower];
-    p1.0 * p2.0
+    points[last_merge.a].0 * points[last_merge.b].0
 }
 
 #[cfg(test)]
@@ -5,6 +5,9 @@ use aoc_runner_derive::{aoc, aoc_generator};
 use itertools::Itertools;
 use rayon::prelude::*;
 
+use crate::utils::compressed_grid::CompressedGrid;
+use crate::utils::grid::Position;
+
 type Point = (isize, isize);
 type Input = Vec<Point>;
 
@@ -35,28 +38,21 @@ fn part1(input: &Input) -> Option<isize> {
 
 #[aoc(day9, part2)]
 fn part2(corners: &Input) -> Option<isize> {
+    let tiles = CompressedGrid::from_polygon(&corners.iter().map(|&(x, y)| Position(x, y)).collect_vec());
+    let filled_area_table = tiles.filled_area_table();
+
     corners
         .iter()
         .tuple_combinations()
-        .par_bridge()
-        .filter(|(p1, p2)| {
-            let x_min = min(p1.0, p2.0);
-            let x_max = max(p1.0, p2.0);
-            let y_min = min(p1.1, p2.1);
-            let y_max = max(p1.1, p2.1);
-
-            corners
-                .iter()
-                .circular_tuple_windows()
-                .all(|(q1, q2)| {
-                    (min(q1.0, q2.0)..=max(q1.0, q2.0)).cartesian_product(min(q1.1, q2.1)..=max(q1.1, q2.1))
-                        .all(|q| q.0 <= x_min || q.0 >= x_max || q.1 <= y_min || q.1 >= y_max)
-                })
-        })
         .map(|(p1, p2)| {
-            (max(p1.0, p2.0) - min(p1.0, p2.0) + 1) * (max(p1.1, p2.1) - min(p1.1, p2.1) + 1)
+            let area = (max(p1.0, p2.0) - min(p1.0, p2.0) + 1) * (max(p1.1, p2.1) - min(p1.1, p2.1) + 1);
+            (area, *p1, *p2)
         })
-        .max()
+        .sorted_unstable_by(|a, b| b.cmp(a))
+        .collect_vec()
+        .par_iter()
+        .find_first(|(_, p1, p2)| tiles.is_filled(&filled_area_table, &Position(p1.0, p1.1), &Position(p2.0, p2.1)))
+        .map(|(area, _, _)| *area)
 }
 
 #[cfg(test)]
@@ -91,8 +87,24 @@ mod tests {
         assert_eq!(Some(24), part2(&parse(EXAMPLE1).unwrap()));
     }
 
-    // #[test]
-    // fn part2_input() {
-    //     assert_eq!(Some(1470616992), part2(&parse(include_str!("../input/2025/day9.txt")).unwrap()));
-    // }
+    #[test]
+    fn part2_counts_tiles_on_both_sides_of_a_zero_width_gap() {
+        let input = indoc! {"
+            0,0
+            0,10
+            1,10
+            1,1
+            2,1
+            2,10
+            3,10
+            3,0
+        "};
+
+        assert_eq!(Some(44), part2(&parse(input).unwrap()));
+    }
+
+    #[test]
+    fn part2_input() {
+        assert_eq!(Some(1470616992), part2(&parse(include_str!("../input/2025/day9.txt")).unwrap()));
+    }
 }
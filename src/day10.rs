@@ -6,7 +6,7 @@ use itertools::Itertools;
 use z3::{ast::Int, Optimize};
 use rayon::prelude::*;
 
-use crate::utils::path_finding::shortest_paths_to_target;
+use crate::utils::path_finding::bidirectional_shortest_paths;
 
 type IndicatorLights = Vec<bool>;
 type WiringSchematics = Vec<Vec<usize>>;
@@ -22,7 +22,7 @@ fn parse(input: &str) -> Result<Input> {
             let (target_lights, rest) = line.split_once(' ').with_context(|| format!("Invalid input: {line}"))?;
             let (button_wirings, joltage_requirements) = rest.rsplit_once(' ').with_context(|| format!("Invalid input: {line}"))?;
 
-            let target_lights = target_lights
+            let target_lights: IndicatorLights = target_lights
                 .strip_prefix('[').with_context(|| format!("Invalid indicator light diagram: {target_lights} (in {line})"))?
                 .strip_suffix(']').with_context(|| format!("Invalid indicator light diagram: {target_lights} (in {line})"))?
                 .chars()
@@ -33,7 +33,11 @@ fn parse(input: &str) -> Result<Input> {
                 })
                 .try_collect()?;
 
-            let button_wirings = button_wirings
+            if target_lights.len() > u64::BITS as usize {
+                bail!("Too many indicator lights: {} (at most {} supported, in {line})", target_lights.len(), u64::BITS);
+            }
+
+            let button_wirings: WiringSchematics = button_wirings
                 .split_ascii_whitespace()
                 .map(|wiring| {
                     wiring
@@ -45,6 +49,10 @@ fn parse(input: &str) -> Result<Input> {
                 })
                 .try_collect()?;
 
+            if let Some(button) = button_wirings.iter().flatten().find(|&&button| button >= target_lights.len()) {
+                bail!("Button wired to missing indicator light: {button} (in {line})");
+            }
+
             let joltage_requirements = joltage_requirements
                 .strip_prefix('{').with_context(|| format!("Invalid joltage requirements: {joltage_requirements}"))?
                 .strip_suffix('}').with_context(|| format!("Invalid joltage requirements: {joltage_requirements}"))?
@@ -57,30 +65,25 @@ fn parse(input: &str) -> Result<Input> {
         .try_collect()
 }
 
+fn to_bitmask(bits: impl IntoIterator<Item = usize>) -> u64 {
+    bits.into_iter().fold(0, |acc, i| acc | 1 << i)
+}
+
 #[aoc(day10, part1)]
 fn part1(input: &Input) -> Option<usize> {
     input
         .iter()
         .map(|(target, wirings, _)| {
-            let (presses, _) = shortest_paths_to_target(
-                vec![false; target.len()],
-                |current| {
-                    wirings
-                        .iter()
-                        .map(|wiring| {
-                            let neighbor = current
-                                .iter()
-                                .enumerate()
-                                .map(|(i, light_state)| light_state ^ wiring.contains(&i))
-                                .collect_vec();
-                            (neighbor, 1)
-                        })
-                        .collect_vec()
-                },
-                |lights| lights == target,
-            )?;
-
-            Some(presses)
+            let target = to_bitmask(target.iter().positions(|&light_state| light_state));
+            let wirings = wirings
+                .iter()
+                .map(|wiring| to_bitmask(wiring.iter().copied()))
+                .collect_vec();
+
+            let neighbors = |&current: &u64| wirings.iter().map(move |wiring| (current ^ wiring, 1));
+            let paths = bidirectional_shortest_paths(0, target, neighbors, neighbors)?;
+
+            Some(paths.distance)
         })
         .sum()
 }
@@ -145,6 +148,15 @@ mod tests {
         assert_eq!(Some(7), part1(&parse(EXAMPLE1).unwrap()));
     }
 
+    #[test]
+    fn parse_rejects_lights_outside_the_mask() {
+        let lights = |count| format!("[{}] (0) {{1}}", ".".repeat(count));
+
+        assert!(parse(&lights(64)).is_ok());
+        assert!(parse(&lights(65)).is_err());
+        assert!(parse("[.#] (0,2) {1,1}").is_err());
+    }
+
     #[test]
     fn part1_input() {
         assert_eq!(Some(409), part1(&parse(include_str!("../input/2025/day10.txt")).unwrap()));
@@ -0,0 +1,277 @@
+#![allow(dead_code)]
+
+use std::hash::Hash;
+
+use hashbrown::HashMap;
+use rayon::prelude::*;
+
+use super::path_finding::{multi_source_distances, Cost};
+
+#[derive(Clone, Debug)]
+pub struct AllPairs<N, C> where N: Hash + Eq {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    distances: Vec<Vec<Option<C>>>,
+    next_hops: Vec<Vec<Option<usize>>>,
+}
+
+impl<N, C> AllPairs<N, C> where N: Clone + Hash + Eq, C: Cost {
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    pub fn index(&self, node: &N) -> Option<usize> {
+        self.indices.get(node).copied()
+    }
+
+    pub fn distance_matrix(&self) -> &[Vec<Option<C>>] {
+        &self.distances
+    }
+
+    pub fn next_hop_matrix(&self) -> &[Vec<Option<usize>>] {
+        &self.next_hops
+    }
+
+    pub fn distance(&self, from: &N, to: &N) -> Option<C> {
+        self.distances[self.index(from)?][self.index(to)?]
+    }
+
+    pub fn next_hop(&self, from: &N, to: &N) -> Option<&N> {
+        self.next_hops[self.index(from)?][self.index(to)?].map(|index| &self.nodes[index])
+    }
+
+    pub fn path(&self, from: &N, to: &N) -> Option<Vec<N>> {
+        let (mut current, to) = (self.index(from)?, self.index(to)?);
+        self.distances[current][to]?;
+
+        let mut path = vec![self.nodes[current].clone()];
+
+        while current != to {
+            current = self.next_hops[current][to]?;
+            path.push(self.nodes[current].clone());
+        }
+
+        Some(path)
+    }
+}
+
+struct DenseGraph<N, C> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    adjacency: Vec<Vec<(usize, C)>>,
+}
+
+impl<N, C> DenseGraph<N, C> where N: Clone + Hash + Eq, C: Cost {
+    fn new<Neighbors>(nodes: impl IntoIterator<Item = N>, neighbors: impl Fn(&N) -> Neighbors) -> DenseGraph<N, C> where
+        Neighbors: IntoIterator<Item = (N, C)>,
+    {
+        let mut unique_nodes = vec![];
+        let mut indices = HashMap::new();
+
+        for node in nodes {
+            indices.entry(node.clone()).or_insert_with(|| {
+                unique_nodes.push(node);
+                unique_nodes.len() - 1
+            });
+        }
+
+        let adjacency = unique_nodes
+            .iter()
+            .map(|node| {
+                neighbors(node)
+                    .into_iter()
+                    .filter_map(|(neighbor, cost)| Some((*indices.get(&neighbor)?, cost)))
+                    .collect()
+            })
+            .collect();
+
+        DenseGraph { nodes: unique_nodes, indices, adjacency }
+    }
+
+    fn into_all_pairs(self, distances: Vec<Vec<Option<C>>>, next_hops: Vec<Vec<Option<usize>>>) -> AllPairs<N, C> {
+        AllPairs {
+            nodes: self.nodes,
+            indices: self.indices,
+            distances,
+            next_hops,
+        }
+    }
+}
+
+pub fn floyd_warshall<N, C, Neighbors>(
+    nodes: impl IntoIterator<Item = N>,
+    neighbors: impl Fn(&N) -> Neighbors,
+) -> AllPairs<N, C> where
+    N: Clone + Hash + Eq,
+    C: Cost,
+    Neighbors: IntoIterator<Item = (N, C)>,
+{
+    let graph = DenseGraph::new(nodes, neighbors);
+    let len = graph.nodes.len();
+    let mut distances: Vec<Vec<Option<C>>> = vec![vec![None; len]; len];
+    let mut next_hops: Vec<Vec<Option<usize>>> = vec![vec![None; len]; len];
+
+    for (from, edges) in graph.adjacency.iter().enumerate() {
+        distances[from][from] = Some(C::default());
+        next_hops[from][from] = Some(from);
+
+        for &(to, cost) in edges {
+            if distances[from][to].is_none_or(|distance| cost < distance) {
+                distances[from][to] = Some(cost);
+                next_hops[from][to] = Some(to);
+            }
+        }
+    }
+
+    for via in 0..len {
+        for from in 0..len {
+            let Some(to_via) = distances[from][via] else { continue; };
+
+            for to in 0..len {
+                let Some(from_via) = distances[via][to] else { continue; };
+                let candidate = to_via + from_via;
+
+                if distances[from][to].is_none_or(|distance| candidate < distance) {
+                    distances[from][to] = Some(candidate);
+                    next_hops[from][to] = next_hops[from][via];
+                }
+            }
+        }
+    }
+
+    graph.into_all_pairs(distances, next_hops)
+}
+
+pub fn all_pairs_dijkstra<N, C, Neighbors>(
+    nodes: impl IntoIterator<Item = N>,
+    neighbors: impl Fn(&N) -> Neighbors,
+) -> AllPairs<N, C> where
+    N: Clone + Hash + Eq + Send + Sync,
+    C: Cost + Send + Sync,
+    Neighbors: IntoIterator<Item = (N, C)>,
+{
+    let graph = DenseGraph::new(nodes, neighbors);
+    let len = graph.nodes.len();
+
+    let (distances, next_hops): (Vec<_>, Vec<_>) = (0..len)
+        .into_par_iter()
+        .map(|source| {
+            let distance_map = multi_source_distances(
+                [(source, None)],
+                |&current| graph.adjacency[current].iter().copied(),
+                |_| false,
+                None,
+            );
+
+            let distances = (0..len).map(|target| distance_map.distance(&target)).collect();
+            let mut next_hops: Vec<Option<usize>> = vec![None; len];
+            next_hops[source] = Some(source);
+
+            for target in 0..len {
+                let mut chain = vec![];
+                let mut current = target;
+
+                while next_hops[current].is_none() {
+                    let Some(&previous) = distance_map.predecessors(&current).first() else { break; };
+                    chain.push(current);
+                    current = previous;
+                }
+
+                let Some(mut hop) = next_hops[current] else { continue; };
+
+                for node in chain.into_iter().rev() {
+                    if current == source {
+                        hop = node;
+                    }
+
+                    next_hops[node] = Some(hop);
+                    current = node;
+                }
+            }
+
+            (distances, next_hops)
+        })
+        .unzip();
+
+    graph.into_all_pairs(distances, next_hops)
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::utils::random::xorshift;
+
+    fn successors(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 4), ('c', 1)],
+            'b' => vec![('d', 1)],
+            'c' => vec![('b', 2), ('d', 5)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn shortest_distances_and_paths() {
+        for all_pairs in [floyd_warshall("abcde".chars(), successors), all_pairs_dijkstra("abcde".chars(), successors)] {
+            assert_eq!(Some(4), all_pairs.distance(&'a', &'d'));
+            assert_eq!(Some(3), all_pairs.distance(&'c', &'d'));
+            assert_eq!(Some(0), all_pairs.distance(&'e', &'e'));
+            assert_eq!(None, all_pairs.distance(&'d', &'a'));
+            assert_eq!(None, all_pairs.distance(&'a', &'e'));
+            assert_eq!(Some(&'c'), all_pairs.next_hop(&'a', &'b'));
+            assert_eq!(Some(vec!['a', 'c', 'b', 'd']), all_pairs.path(&'a', &'d'));
+            assert_eq!(Some(vec!['b']), all_pairs.path(&'b', &'b'));
+            assert_eq!(None, all_pairs.path(&'d', &'b'));
+        }
+    }
+
+    #[test]
+    fn floyd_warshall_matches_all_pairs_dijkstra() {
+        let mut random = xorshift(23);
+
+        for _ in 0..200 {
+            let len = (random() % 8 + 1) as usize;
+            let adjacency = (0..len)
+                .map(|_| {
+                    (0..random() % 4)
+                        .map(|_| ((random() % len as u64) as usize, random() % 10))
+                        .collect_vec()
+                })
+                .collect_vec();
+            let neighbors = |node: &usize| adjacency[*node].clone();
+
+            let floyd_warshall = floyd_warshall(0..len, neighbors);
+            let dijkstra = all_pairs_dijkstra(0..len, neighbors);
+
+            assert_eq!(floyd_warshall.distance_matrix(), dijkstra.distance_matrix());
+
+            for (from, to) in (0..len).cartesian_product(0..len) {
+                for all_pairs in [&floyd_warshall, &dijkstra] {
+                    let Some(path) = all_pairs.path(&from, &to) else {
+                        assert_eq!(None, all_pairs.distance(&from, &to));
+                        continue;
+                    };
+
+                    let cost = path
+                        .iter()
+                        .tuple_windows()
+                        .map(|(a, b)| adjacency[*a].iter().filter(|(next, _)| next == b).map(|(_, cost)| *cost).min().unwrap())
+                        .sum::<u64>();
+
+                    assert_eq!((from, to), (path[0], path[path.len() - 1]));
+                    assert_eq!(all_pairs.distance(&from, &to), Some(cost));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,390 @@
+#![allow(dead_code)]
+
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+use itertools::Itertools;
+
+use super::grid::{Grid, Neighborhood, Position};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BitGrid {
+    store: Vec<u64>,
+    rows: usize,
+    cols: usize,
+    words_per_row: usize,
+}
+
+impl BitGrid {
+    pub fn new(rows: usize, cols: usize) -> BitGrid {
+        let words_per_row = cols.div_ceil(WORD_BITS);
+
+        BitGrid {
+            store: vec![0; rows * words_per_row],
+            rows,
+            cols,
+            words_per_row,
+        }
+    }
+
+    pub fn filled(rows: usize, cols: usize) -> BitGrid {
+        !&BitGrid::new(rows, cols)
+    }
+
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, &Position(i, j): &Position) -> Option<bool> {
+        if i < 0 || i as usize >= self.rows || j < 0 || j as usize >= self.cols {
+            return None;
+        }
+
+        let (word, bit) = self.locate(i as usize, j as usize);
+        Some(self.store[word] >> bit & 1 == 1)
+    }
+
+    pub fn set(&mut self, &Position(i, j): &Position, value: bool) {
+        if i < 0 || i as usize >= self.rows || j < 0 || j as usize >= self.cols {
+            panic!("Position out of bounds: ({i}, {j})");
+        }
+
+        let (word, bit) = self.locate(i as usize, j as usize);
+
+        if value {
+            self.store[word] |= 1 << bit;
+        } else {
+            self.store[word] &= !(1 << bit);
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.store.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.store.iter().all(|&word| word == 0)
+    }
+
+    pub fn iter_ones(&self) -> impl Iterator<Item = Position> + '_ {
+        self.store
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, &word)| {
+                let i = index / self.words_per_row;
+                let j_offset = (index % self.words_per_row) * WORD_BITS;
+
+                (0..WORD_BITS)
+                    .filter(move |bit| word >> bit & 1 == 1)
+                    .map(move |bit| Position::new(i, j_offset + bit))
+            })
+    }
+
+    pub fn shifted(&self, di: isize, dj: isize) -> BitGrid {
+        let mut result = BitGrid::new(self.rows, self.cols);
+
+        for i in 0..self.rows {
+            let source_i = i as isize - di;
+
+            if source_i < 0 || source_i as usize >= self.rows {
+                continue;
+            }
+
+            let source = self.row(source_i as usize);
+            let target = result.row_mut(i);
+
+            for (w, word) in target.iter_mut().enumerate() {
+                *word = Self::shifted_word(source, w, dj);
+            }
+        }
+
+        result.mask_tail();
+        result
+    }
+
+    pub fn neighbor_counts(&self, neighborhood: Neighborhood) -> Grid<u32> {
+        let planes = self.neighbor_count_planes(neighborhood);
+        let mut counts = Grid::new(self.rows, self.cols);
+
+        for (bit, plane) in planes.iter().enumerate() {
+            for position in plane.iter_ones() {
+                counts[(position.0, position.1)] |= 1 << bit;
+            }
+        }
+
+        counts
+    }
+
+    pub fn neighbor_count_less_than(&self, neighborhood: Neighborhood, k: usize) -> BitGrid {
+        let planes = self.neighbor_count_planes(neighborhood);
+
+        if k >> planes.len() > 0 {
+            return BitGrid::filled(self.rows, self.cols);
+        }
+
+        let mut less = BitGrid::new(self.rows, self.cols);
+        let mut equal = BitGrid::filled(self.rows, self.cols);
+
+        for (bit, plane) in planes.iter().enumerate().rev() {
+            if k >> bit & 1 == 1 {
+                less |= &(&equal & &!plane);
+                equal &= plane;
+            } else {
+                equal &= &!plane;
+            }
+        }
+
+        less
+    }
+
+    pub fn neighbor_count_at_least(&self, neighborhood: Neighborhood, k: usize) -> BitGrid {
+        !&self.neighbor_count_less_than(neighborhood, k)
+    }
+
+    fn neighbor_count_planes(&self, neighborhood: Neighborhood) -> Vec<BitGrid> {
+        let mut planes: Vec<BitGrid> = vec![];
+
+        for (di, dj) in neighborhood.offsets() {
+            let mut carry = self.shifted(di, dj);
+
+            for plane in planes.iter_mut() {
+                if carry.is_empty() {
+                    break;
+                }
+
+                let next_carry = &*plane & &carry;
+                *plane ^= &carry;
+                carry = next_carry;
+            }
+
+            if !carry.is_empty() {
+                planes.push(carry);
+            }
+        }
+
+        planes
+    }
+
+    fn shifted_word(source: &[u64], w: usize, dj: isize) -> u64 {
+        let word_at = |index: isize| -> u64 {
+            if index < 0 || index as usize >= source.len() { 0 } else { source[index as usize] }
+        };
+
+        let words = dj.div_euclid(WORD_BITS as isize);
+        let bits = dj.rem_euclid(WORD_BITS as isize) as u32;
+        let low = word_at(w as isize - words);
+
+        if bits == 0 {
+            low
+        } else {
+            low << bits | word_at(w as isize - words - 1) >> (WORD_BITS as u32 - bits)
+        }
+    }
+
+    fn locate(&self, i: usize, j: usize) -> (usize, usize) {
+        (i * self.words_per_row + j / WORD_BITS, j % WORD_BITS)
+    }
+
+    fn row(&self, i: usize) -> &[u64] {
+        &self.store[i * self.words_per_row..(i + 1) * self.words_per_row]
+    }
+
+    fn row_mut(&mut self, i: usize) -> &mut [u64] {
+        &mut self.store[i * self.words_per_row..(i + 1) * self.words_per_row]
+    }
+
+    fn mask_tail(&mut self) {
+        let tail_bits = self.cols % WORD_BITS;
+
+        if tail_bits == 0 {
+            return;
+        }
+
+        let mask = (1 << tail_bits) - 1;
+
+        for i in 0..self.rows {
+            if let Some(word) = self.row_mut(i).last_mut() {
+                *word &= mask;
+            }
+        }
+    }
+
+    fn zip_with(&mut self, other: &BitGrid, f: impl Fn(u64, u64) -> u64) {
+        if self.dimensions() != other.dimensions() {
+            panic!("Mismatched BitGrid dimensions: {:?} and {:?}", self.dimensions(), other.dimensions());
+        }
+
+        for (a, &b) in self.store.iter_mut().zip_eq(&other.store) {
+            *a = f(*a, b);
+        }
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, rhs: &BitGrid) {
+        self.zip_with(rhs, |a, b| a & b);
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, rhs: &BitGrid) {
+        self.zip_with(rhs, |a, b| a | b);
+    }
+}
+
+impl BitXorAssign<&BitGrid> for BitGrid {
+    fn bitxor_assign(&mut self, rhs: &BitGrid) {
+        self.zip_with(rhs, |a, b| a ^ b);
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, rhs: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result &= rhs;
+        result
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, rhs: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result |= rhs;
+        result
+    }
+}
+
+impl BitXor for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitxor(self, rhs: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result ^= rhs;
+        result
+    }
+}
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mut result = self.clone();
+
+        for word in result.store.iter_mut() {
+            *word = !*word;
+        }
+
+        result.mask_tail();
+        result
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let mut bit_grid = BitGrid::new(grid.rows(), grid.cols());
+
+        for (position, &value) in grid {
+            if value {
+                bit_grid.set(&position, true);
+            }
+        }
+
+        bit_grid
+    }
+}
+
+impl From<&BitGrid> for Grid<bool> {
+    fn from(bit_grid: &BitGrid) -> Self {
+        let mut grid = Grid::new(bit_grid.rows, bit_grid.cols);
+
+        for position in bit_grid.iter_ones() {
+            grid.set(&position, true);
+        }
+
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::random::xorshift;
+
+    fn random_grids() -> impl Iterator<Item = Grid<bool>> {
+        let mut random = xorshift(5);
+
+        (0..40).map(move |_| {
+            let (rows, cols) = ((random() % 8 + 1) as usize, (random() % 150 + 1) as usize);
+            let mut grid = Grid::new(rows, cols);
+
+            for (i, j) in (0..rows).cartesian_product(0..cols) {
+                grid.set(&Position::new(i, j), random().is_multiple_of(3));
+            }
+
+            grid
+        })
+    }
+
+    #[test]
+    fn round_trips_through_grid() {
+        for grid in random_grids() {
+            let bit_grid = BitGrid::from(&grid);
+
+            assert_eq!((&grid).into_iter().filter(|&(_, &value)| value).count(), bit_grid.count_ones());
+            assert!((&grid).into_iter().all(|(position, &value)| bit_grid.get(&position) == Some(value)));
+
+            let round_trip: Grid<bool> = Grid::from(&bit_grid);
+            assert!((&grid).into_iter().all(|(position, value)| round_trip.get(&position) == Some(value)));
+        }
+    }
+
+    #[test]
+    fn neighbor_counts_match_grid_neighbors() {
+        let neighborhoods = [
+            Neighborhood::VonNeumann,
+            Neighborhood::Moore,
+            Neighborhood::VonNeumannRadius(3),
+            Neighborhood::MooreRadius(2),
+        ];
+
+        for grid in random_grids() {
+            let bit_grid = BitGrid::from(&grid);
+
+            for neighborhood in neighborhoods {
+                let counts = bit_grid.neighbor_counts(neighborhood);
+                let at_least_three = bit_grid.neighbor_count_at_least(neighborhood, 3);
+
+                for (position, _) in &grid {
+                    let expected = grid.neighbors(&position, neighborhood).filter(|&(_, &value)| value).count();
+
+                    assert_eq!(expected as u32, counts[(position.0, position.1)], "{neighborhood:?} at {position:?}");
+                    assert_eq!(Some(expected >= 3), at_least_three.get(&position));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shifted_moves_bits_and_drops_overflow() {
+        let mut bit_grid = BitGrid::new(2, 70);
+        bit_grid.set(&Position(0, 63), true);
+        bit_grid.set(&Position(1, 69), true);
+
+        assert_eq!(vec![Position(1, 64)], bit_grid.shifted(1, 1).iter_ones().collect_vec());
+        assert_eq!(vec![Position(0, 62), Position(1, 68)], bit_grid.shifted(0, -1).iter_ones().collect_vec());
+        assert_eq!(vec![Position(0, 0)], bit_grid.shifted(-1, -69).iter_ones().collect_vec());
+    }
+}
@@ -0,0 +1,310 @@
+#![allow(dead_code)]
+
+use std::mem;
+
+use itertools::Itertools;
+
+use super::grid::{Grid, Neighborhood, Position};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum UpdateMode {
+    #[default]
+    Synchronous,
+    Asynchronous,
+    Incremental,
+}
+
+pub struct Simulation {
+    pub generations: usize,
+    pub fixed_point: bool,
+    pub changes: Vec<Vec<Position>>,
+}
+
+pub struct Automaton<T, R> where
+    T: Clone + PartialEq,
+    R: Fn(&Grid<T>, &Position, &T) -> T,
+{
+    grid: Grid<T>,
+    buffer: Grid<T>,
+    queued: Grid<bool>,
+    rule: R,
+    mode: UpdateMode,
+    neighborhood: Neighborhood,
+    generation: usize,
+    worklist: Option<Vec<Position>>,
+}
+
+impl<T, R> Automaton<T, R> where
+    T: Clone + PartialEq,
+    R: Fn(&Grid<T>, &Position, &T) -> T,
+{
+    pub fn new(grid: Grid<T>, rule: R) -> Automaton<T, R> {
+        Automaton {
+            buffer: grid.clone(),
+            queued: Grid::new(grid.rows::<usize>(), grid.cols::<usize>()),
+            grid,
+            rule,
+            mode: UpdateMode::default(),
+            neighborhood: Neighborhood::Moore,
+            generation: 0,
+            worklist: None,
+        }
+    }
+
+    pub fn with_mode(mut self, mode: UpdateMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// In `UpdateMode::Incremental`, only cells within this neighborhood of a change are
+    /// re-evaluated in the next generation, so the rule must not look beyond it.
+    pub fn with_neighborhood(mut self, neighborhood: Neighborhood) -> Self {
+        self.neighborhood = neighborhood;
+        self
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn step(&mut self) -> Vec<Position> {
+        let changes = match self.mode {
+            UpdateMode::Synchronous => self.step_synchronous(),
+            UpdateMode::Asynchronous => self.step_asynchronous(),
+            UpdateMode::Incremental => self.step_incremental(),
+        };
+
+        self.generation += 1;
+        changes
+    }
+
+    pub fn run(&mut self, max_generations: Option<usize>) -> Simulation {
+        let mut changes = vec![];
+
+        while max_generations.is_none_or(|max_generations| changes.len() < max_generations) {
+            let generation_changes = self.step();
+
+            if generation_changes.is_empty() {
+                return Simulation {
+                    generations: changes.len(),
+                    fixed_point: true,
+                    changes,
+                };
+            }
+
+            changes.push(generation_changes);
+        }
+
+        Simulation {
+            generations: changes.len(),
+            fixed_point: false,
+            changes,
+        }
+    }
+
+    fn step_synchronous(&mut self) -> Vec<Position> {
+        let mut changes = vec![];
+        self.buffer.clone_from(&self.grid);
+
+        for (position, value) in &self.grid {
+            let next_value = (self.rule)(&self.grid, &position, value);
+
+            if next_value != *value {
+                self.buffer.set(&position, next_value);
+                changes.push(position);
+            }
+        }
+
+        mem::swap(&mut self.grid, &mut self.buffer);
+        changes
+    }
+
+    fn step_asynchronous(&mut self) -> Vec<Position> {
+        let mut changes = vec![];
+        let (rows, cols) = self.grid.dimensions::<usize>();
+
+        for position in (0..rows).cartesian_product(0..cols).map(|(i, j)| Position::new(i, j)) {
+            let value = &self.grid[(position.0, position.1)];
+            let next_value = (self.rule)(&self.grid, &position, value);
+
+            if next_value != *value {
+                self.grid.set(&position, next_value);
+                changes.push(position);
+            }
+        }
+
+        changes
+    }
+
+    fn step_incremental(&mut self) -> Vec<Position> {
+        let worklist = self.worklist.take().unwrap_or_else(|| {
+            (&self.grid).into_iter().map(|(position, _)| position).collect()
+        });
+
+        let changes = worklist
+            .into_iter()
+            .filter_map(|position| {
+                let value = &self.grid[(position.0, position.1)];
+                let next_value = (self.rule)(&self.grid, &position, value);
+                (next_value != *value).then_some((position, next_value))
+            })
+            .collect_vec();
+
+        let mut worklist = vec![];
+
+        for (position, next_value) in changes.iter().cloned() {
+            self.grid.set(&position, next_value);
+
+            let affected = self.grid
+                .neighbors(&position, self.neighborhood)
+                .map(|(neighbor, _)| neighbor)
+                .chain([position]);
+
+            for neighbor in affected {
+                if !self.queued[(neighbor.0, neighbor.1)] {
+                    self.queued.set(&neighbor, true);
+                    worklist.push(neighbor);
+                }
+            }
+        }
+
+        for position in &worklist {
+            self.queued.set(position, false);
+        }
+
+        worklist.sort();
+        self.worklist = Some(worklist);
+
+        changes.into_iter().map(|(position, _)| position).collect()
+    }
+}
+
+impl<T, R> Iterator for Automaton<T, R> where
+    T: Clone + PartialEq,
+    R: Fn(&Grid<T>, &Position, &T) -> T,
+{
+    type Item = Vec<Position>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let changes = self.step();
+        (!changes.is_empty()).then_some(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::utils::grid::Direction;
+
+    fn cells(input: &str) -> Grid<bool> {
+        let chars: Grid<char> = Grid::parse(input).unwrap();
+        let mut grid = Grid::new(chars.rows::<usize>(), chars.cols::<usize>());
+
+        for (position, &c) in &chars {
+            grid.set(&position, c == '#');
+        }
+
+        grid
+    }
+
+    fn game_of_life(grid: &Grid<bool>, position: &Position, &alive: &bool) -> bool {
+        let live_neighbors = grid.neighbors(position, Neighborhood::Moore).filter(|&(_, &neighbor)| neighbor).count();
+        live_neighbors == 3 || (alive && live_neighbors == 2)
+    }
+
+    fn spread_right(grid: &Grid<bool>, position: &Position, &value: &bool) -> bool {
+        value || grid.get(&position.step(Direction::Left)).copied().unwrap_or(false)
+    }
+
+    const GLIDER: &str = indoc! {"
+        .#......
+        ..#.....
+        ###.....
+        ........
+        ........
+        ........
+        ........
+        ........
+    "};
+
+    #[test]
+    fn incremental_matches_synchronous() {
+        let mut synchronous = Automaton::new(cells(GLIDER), game_of_life);
+        let mut incremental = Automaton::new(cells(GLIDER), game_of_life).with_mode(UpdateMode::Incremental);
+
+        for _ in 0..40 {
+            let mut changes = synchronous.step();
+            let mut incremental_changes = incremental.step();
+            changes.sort();
+            incremental_changes.sort();
+
+            assert_eq!(changes, incremental_changes);
+            assert_eq!(synchronous.grid().to_string(), incremental.grid().to_string());
+        }
+
+        assert_eq!(40, incremental.generation());
+        let block = indoc! {"
+            ........
+            ........
+            ........
+            ........
+            ........
+            ........
+            ......##
+            ......##
+        "};
+
+        assert_eq!(cells(block).to_string(), synchronous.grid().to_string());
+    }
+
+    #[test]
+    fn asynchronous_updates_see_earlier_changes() {
+        let row = cells("#....\n");
+
+        let simulation = Automaton::new(row.clone(), spread_right).run(None);
+        assert_eq!(4, simulation.generations);
+        assert_eq!(vec![Position(0, 1)], simulation.changes[0]);
+
+        let mut automaton = Automaton::new(row, spread_right).with_mode(UpdateMode::Asynchronous);
+        let simulation = automaton.run(None);
+        assert_eq!(1, simulation.generations);
+        assert_eq!(vec![Position(0, 1), Position(0, 2), Position(0, 3), Position(0, 4)], simulation.changes[0]);
+        assert_eq!("#####", automaton.grid().to_string());
+    }
+
+    #[test]
+    fn run_stops_at_fixed_point_or_max_generations() {
+        let blinker = indoc! {"
+            .....
+            ..#..
+            ..#..
+            ..#..
+            .....
+        "};
+
+        let simulation = Automaton::new(cells(blinker), game_of_life).run(Some(5));
+        assert_eq!(5, simulation.generations);
+        assert!(!simulation.fixed_point);
+        assert!(simulation.changes.iter().all(|changes| changes.len() == 4));
+
+        let mut automaton = Automaton::new(cells(GLIDER), game_of_life);
+        let simulation = automaton.run(None);
+        assert!(simulation.fixed_point);
+        assert_eq!(simulation.generations, automaton.generation() - 1);
+        assert_eq!(None, automaton.next());
+
+        let simulation = Automaton::new(cells(".##\n.##\n"), game_of_life).run(Some(3));
+        assert!(simulation.fixed_point);
+        assert_eq!(0, simulation.generations);
+    }
+}
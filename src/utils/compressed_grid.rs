@@ -0,0 +1,268 @@
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+
+use itertools::Itertools;
+
+use super::grid::{Grid, Neighborhood, Position};
+use super::summed_area::FilledAreaTable;
+
+#[derive(Clone, Debug)]
+pub struct Axis {
+    starts: Vec<isize>,
+    ends: Vec<isize>,
+}
+
+impl Axis {
+    pub fn new(coordinates: impl IntoIterator<Item = isize>) -> Axis {
+        let coordinates = coordinates.into_iter().sorted().dedup().collect_vec();
+        let mut starts = vec![];
+        let mut ends = vec![];
+
+        for (index, &coordinate) in coordinates.iter().enumerate() {
+            if index > 0 && coordinates[index - 1] + 1 < coordinate {
+                starts.push(coordinates[index - 1] + 1);
+                ends.push(coordinate - 1);
+            }
+
+            starts.push(coordinate);
+            ends.push(coordinate);
+        }
+
+        Axis { starts, ends }
+    }
+
+    pub fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    pub fn compress(&self, coordinate: isize) -> Option<usize> {
+        let index = self.ends.partition_point(|&end| end < coordinate);
+        (index < self.len() && self.starts[index] <= coordinate).then_some(index)
+    }
+
+    pub fn range(&self, index: usize) -> (isize, isize) {
+        (self.starts[index], self.ends[index])
+    }
+
+    pub fn width(&self, index: usize) -> usize {
+        (self.ends[index] - self.starts[index] + 1) as usize
+    }
+}
+
+#[derive(Clone)]
+pub struct CompressedGrid<T> where T: Clone {
+    grid: Grid<T>,
+    rows: Axis,
+    cols: Axis,
+}
+
+impl<T> CompressedGrid<T> where T: Clone {
+    pub fn new(
+        rows: impl IntoIterator<Item = isize>,
+        cols: impl IntoIterator<Item = isize>,
+        value: T,
+    ) -> CompressedGrid<T> {
+        let rows = Axis::new(rows);
+        let cols = Axis::new(cols);
+
+        CompressedGrid {
+            grid: Grid::new_with_value(rows.len(), cols.len(), value),
+            rows,
+            cols,
+        }
+    }
+
+    pub fn from_positions<'a>(positions: impl IntoIterator<Item = &'a Position>, value: T) -> CompressedGrid<T> {
+        let (rows, cols): (Vec<_>, Vec<_>) = positions
+            .into_iter()
+            .map(|&Position(i, j)| (i, j))
+            .unzip();
+
+        Self::new(rows, cols, value)
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn grid_mut(&mut self) -> &mut Grid<T> {
+        &mut self.grid
+    }
+
+    pub fn row_axis(&self) -> &Axis {
+        &self.rows
+    }
+
+    pub fn col_axis(&self) -> &Axis {
+        &self.cols
+    }
+
+    pub fn compress(&self, &Position(i, j): &Position) -> Option<Position> {
+        Some(Position::new(self.rows.compress(i)?, self.cols.compress(j)?))
+    }
+
+    pub fn decompress(&self, &Position(i, j): &Position) -> Option<(Position, Position)> {
+        if i < 0 || i as usize >= self.rows.len() || j < 0 || j as usize >= self.cols.len() {
+            return None;
+        }
+
+        let (i_start, i_end) = self.rows.range(i as usize);
+        let (j_start, j_end) = self.cols.range(j as usize);
+
+        Some((Position(i_start, j_start), Position(i_end, j_end)))
+    }
+
+    pub fn cell_size(&self, &Position(i, j): &Position) -> (usize, usize) {
+        (self.rows.width(i as usize), self.cols.width(j as usize))
+    }
+
+    pub fn cell_area(&self, position: &Position) -> usize {
+        let (height, width) = self.cell_size(position);
+        height * width
+    }
+
+    pub fn area(&self, p1: &Position, p2: &Position) -> usize {
+        let height: usize = (p1.0.min(p2.0)..=p1.0.max(p2.0)).map(|i| self.rows.width(i as usize)).sum();
+        let width: usize = (p1.1.min(p2.1)..=p1.1.max(p2.1)).map(|j| self.cols.width(j as usize)).sum();
+
+        height * width
+    }
+
+    pub fn get(&self, position: &Position) -> Option<&T> {
+        self.grid.get(&self.compress(position)?)
+    }
+
+    pub fn get_mut(&mut self, position: &Position) -> Option<&mut T> {
+        let position = self.compress(position)?;
+        self.grid.get_mut(&position)
+    }
+
+    pub fn set(&mut self, position: &Position, value: T) {
+        let position = self.compress(position).unwrap_or_else(|| panic!("Position not on compressed grid: {position:?}"));
+        self.grid.set(&position, value);
+    }
+}
+
+impl CompressedGrid<bool> {
+    /// Marks every tile on or inside the rectilinear polygon through `corners`, treating each
+    /// corner as a whole tile. Unlike `geometry::rectangle_inside_polygon`, which works on the
+    /// continuous outline, adjacent boundary tiles count as filled even where the outline leaves a
+    /// zero-width gap between them.
+    pub fn from_polygon(corners: &[Position]) -> CompressedGrid<bool> {
+        if corners.is_empty() {
+            return CompressedGrid::new([], [], false);
+        }
+
+        let padding = corners.iter().flat_map(|&Position(i, j)| [Position(i - 1, j - 1), Position(i + 1, j + 1)]);
+        let positions = corners.iter().copied().chain(padding).collect_vec();
+        let mut boundary = CompressedGrid::from_positions(&positions, false);
+
+        for (p1, p2) in corners.iter().circular_tuple_windows() {
+            let (Some(Position(i1, j1)), Some(Position(i2, j2))) = (boundary.compress(p1), boundary.compress(p2)) else {
+                continue;
+            };
+
+            for i in i1.min(i2)..=i1.max(i2) {
+                for j in j1.min(j2)..=j1.max(j2) {
+                    boundary.grid.set(&Position(i, j), true);
+                }
+            }
+        }
+
+        let mut outside = Grid::new(boundary.grid.rows::<usize>(), boundary.grid.cols::<usize>());
+        let mut queue = VecDeque::from([Position(0, 0)]);
+        outside.set(&Position(0, 0), true);
+
+        while let Some(current) = queue.pop_front() {
+            for (neighbor, &is_boundary) in boundary.grid.neighbors(&current, Neighborhood::VonNeumann) {
+                if !is_boundary && !outside[(neighbor.0, neighbor.1)] {
+                    outside.set(&neighbor, true);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        for (position, is_outside) in outside {
+            boundary.grid.set(&position, !is_outside);
+        }
+
+        boundary
+    }
+
+    pub fn filled_area_table(&self) -> FilledAreaTable {
+        self.grid.filled_area_table()
+    }
+
+    pub fn is_filled(&self, filled_area_table: &FilledAreaTable, p1: &Position, p2: &Position) -> bool {
+        let (Some(c1), Some(c2)) = (self.compress(p1), self.compress(p2)) else {
+            return false;
+        };
+
+        filled_area_table.is_filled(&c1, &c2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::geometry::{self, Containment};
+
+    fn polygon(points: &[(isize, isize)]) -> Vec<Position> {
+        points.iter().map(|&(i, j)| Position(i, j)).collect()
+    }
+
+    const NOTCHED: [(isize, isize); 8] = [(15, 0), (15, 11), (22, 11), (22, 2), (23, 2), (23, 3), (29, 3), (29, 0)];
+    const STAIRS: [(isize, isize); 8] = [(0, 0), (0, 6), (2, 6), (2, 4), (5, 4), (5, 9), (8, 9), (8, 0)];
+
+    #[test]
+    fn axis_compresses_gaps_between_coordinates() {
+        let axis = Axis::new([7, 2, 3, 3]);
+
+        assert_eq!(4, axis.len());
+        assert_eq!(vec![(2, 2), (3, 3), (4, 6), (7, 7)], (0..axis.len()).map(|index| axis.range(index)).collect_vec());
+        assert_eq!(Some(2), axis.compress(5));
+        assert_eq!(3, axis.width(2));
+        assert_eq!(None, axis.compress(8));
+        assert_eq!(None, axis.compress(1));
+    }
+
+    #[test]
+    fn from_polygon_matches_point_containment() {
+        for corners in [polygon(&NOTCHED), polygon(&STAIRS)] {
+            let grid = CompressedGrid::from_polygon(&corners);
+            let points = corners.iter().map(|&Position(i, j)| (i, j)).collect_vec();
+
+            for (i, j) in (-1..=30).cartesian_product(-1..=12) {
+                let expected = geometry::locate_point(&points, (i, j)) != Containment::Outside;
+                assert_eq!(expected, grid.get(&Position(i, j)).copied().unwrap_or(false), "({i}, {j})");
+            }
+        }
+    }
+
+    #[test]
+    fn tile_fill_differs_from_continuous_geometry() {
+        let corners = polygon(&NOTCHED);
+        let grid = CompressedGrid::from_polygon(&corners);
+        let table = grid.filled_area_table();
+        let (p1, p2) = (Position(15, 0), Position(23, 3));
+
+        assert!(grid.is_filled(&table, &p1, &p2));
+        assert!(!geometry::rectangle_inside_polygon(&NOTCHED, (15, 0), (23, 3)));
+        assert_eq!(36, grid.area(&grid.compress(&p1).unwrap(), &grid.compress(&p2).unwrap()));
+    }
+
+    #[test]
+    fn from_polygon_without_corners_is_empty() {
+        let grid = CompressedGrid::from_polygon(&[]);
+        let table = grid.filled_area_table();
+
+        assert_eq!(0, grid.grid().len::<usize>());
+        assert_eq!(None, grid.get(&Position(0, 0)));
+        assert!(!grid.is_filled(&table, &Position(0, 0), &Position(1, 1)));
+    }
+}
@@ -0,0 +1,252 @@
+#![allow(dead_code)]
+
+use std::cmp::{max, min, Ordering};
+
+use itertools::Itertools;
+
+pub type Point = (isize, isize);
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Containment {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+fn cross(o: Point, a: Point, b: Point) -> i128 {
+    (a.0 - o.0) as i128 * (b.1 - o.1) as i128 - (a.1 - o.1) as i128 * (b.0 - o.0) as i128
+}
+
+fn orientation(o: Point, a: Point, b: Point) -> Ordering {
+    cross(o, a, b).cmp(&0)
+}
+
+fn edges(polygon: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    polygon.iter().copied().circular_tuple_windows()
+}
+
+pub fn on_segment(p: Point, a: Point, b: Point) -> bool {
+    orientation(a, b, p) == Ordering::Equal
+        && min(a.0, b.0) <= p.0 && p.0 <= max(a.0, b.0)
+        && min(a.1, b.1) <= p.1 && p.1 <= max(a.1, b.1)
+}
+
+pub fn segments_intersect(a1: Point, a2: Point, b1: Point, b2: Point) -> bool {
+    segments_cross_properly(a1, a2, b1, b2)
+        || on_segment(a1, b1, b2)
+        || on_segment(a2, b1, b2)
+        || on_segment(b1, a1, a2)
+        || on_segment(b2, a1, a2)
+}
+
+pub fn segments_cross_properly(a1: Point, a2: Point, b1: Point, b2: Point) -> bool {
+    let d1 = orientation(b1, b2, a1);
+    let d2 = orientation(b1, b2, a2);
+    let d3 = orientation(a1, a2, b1);
+    let d4 = orientation(a1, a2, b2);
+
+    d1 == d2.reverse() && d1 != Ordering::Equal && d3 == d4.reverse() && d3 != Ordering::Equal
+}
+
+pub fn doubled_signed_area(polygon: &[Point]) -> i128 {
+    edges(polygon)
+        .map(|(a, b)| a.0 as i128 * b.1 as i128 - b.0 as i128 * a.1 as i128)
+        .sum()
+}
+
+pub fn area(polygon: &[Point]) -> isize {
+    (doubled_signed_area(polygon).abs() / 2) as isize
+}
+
+pub fn perimeter(polygon: &[Point]) -> f64 {
+    edges(polygon)
+        .map(|(a, b)| (((a.0 - b.0).pow(2) + (a.1 - b.1).pow(2)) as f64).sqrt())
+        .sum()
+}
+
+pub fn rectilinear_perimeter(polygon: &[Point]) -> isize {
+    edges(polygon)
+        .map(|(a, b)| (a.0 - b.0).abs() + (a.1 - b.1).abs())
+        .sum()
+}
+
+pub fn winding_number(polygon: &[Point], point: Point) -> isize {
+    winding_number_scaled(polygon, point, 1)
+}
+
+fn winding_number_scaled(polygon: &[Point], point: Point, scale: isize) -> isize {
+    scaled_edges(polygon, scale)
+        .map(|(a, b)| {
+            if a.1 <= point.1 && b.1 > point.1 && orientation(a, b, point) == Ordering::Greater {
+                1
+            } else if a.1 > point.1 && b.1 <= point.1 && orientation(a, b, point) == Ordering::Less {
+                -1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+fn scaled_edges(polygon: &[Point], scale: isize) -> impl Iterator<Item = (Point, Point)> + '_ {
+    edges(polygon).map(move |(a, b)| ((a.0 * scale, a.1 * scale), (b.0 * scale, b.1 * scale)))
+}
+
+pub fn locate_point(polygon: &[Point], point: Point) -> Containment {
+    locate_scaled_point(polygon, point, 1)
+}
+
+fn locate_scaled_point(polygon: &[Point], point: Point, scale: isize) -> Containment {
+    if scaled_edges(polygon, scale).any(|(a, b)| on_segment(point, a, b)) {
+        Containment::Boundary
+    } else if winding_number_scaled(polygon, point, scale) != 0 {
+        Containment::Inside
+    } else {
+        Containment::Outside
+    }
+}
+
+pub fn contains(polygon: &[Point], point: Point) -> bool {
+    locate_point(polygon, point) != Containment::Outside
+}
+
+fn contains_midpoint(polygon: &[Point], a: Point, b: Point) -> bool {
+    locate_scaled_point(polygon, (a.0 + b.0, a.1 + b.1), 2) != Containment::Outside
+}
+
+fn segment_meets_open_rectangle(a: Point, b: Point, min_corner: Point, max_corner: Point) -> bool {
+    let (dx, dy) = ((b.0 - a.0) as i128, (b.1 - a.1) as i128);
+    let constraints = [
+        (-dx, (a.0 - min_corner.0) as i128),
+        (dx, (max_corner.0 - a.0) as i128),
+        (-dy, (a.1 - min_corner.1) as i128),
+        (dy, (max_corner.1 - a.1) as i128),
+    ];
+
+    let (mut lower, mut upper) = ((0, 1), (1, 1));
+
+    for (p, q) in constraints {
+        if p == 0 {
+            if q <= 0 {
+                return false;
+            }
+        } else {
+            let bound = if p < 0 { (-q, -p) } else { (q, p) };
+
+            if p < 0 && bound.0 * lower.1 > lower.0 * bound.1 {
+                lower = bound;
+            } else if p > 0 && bound.0 * upper.1 < upper.0 * bound.1 {
+                upper = bound;
+            }
+        }
+    }
+
+    lower.0 * upper.1 < upper.0 * lower.1
+}
+
+/// Checks the rectangle against the continuous outline through the polygon's vertices, so it
+/// fails as soon as any part of it lies outside. See `CompressedGrid::from_polygon` for the
+/// tile-based alternative that counts boundary tiles as filled.
+pub fn rectangle_inside_polygon(polygon: &[Point], p1: Point, p2: Point) -> bool {
+    let min_corner = (min(p1.0, p2.0), min(p1.1, p2.1));
+    let max_corner = (max(p1.0, p2.0), max(p1.1, p2.1));
+
+    if min_corner.0 == max_corner.0 || min_corner.1 == max_corner.1 {
+        return segment_inside_polygon(polygon, min_corner, max_corner);
+    }
+
+    if edges(polygon).any(|(a, b)| segment_meets_open_rectangle(a, b, min_corner, max_corner)) {
+        return false;
+    }
+
+    contains_midpoint(polygon, min_corner, max_corner)
+}
+
+pub fn segment_inside_polygon(polygon: &[Point], a: Point, b: Point) -> bool {
+    if edges(polygon).any(|(c, d)| segments_cross_properly(a, b, c, d)) {
+        return false;
+    }
+
+    let breakpoints = polygon
+        .iter()
+        .copied()
+        .filter(|&p| on_segment(p, a, b))
+        .chain([a, b])
+        .sorted_by_key(|&p| max((p.0 - a.0).abs(), (p.1 - a.1).abs()))
+        .dedup()
+        .collect_vec();
+
+    contains(polygon, a)
+        && contains(polygon, b)
+        && breakpoints.iter().tuple_windows().all(|(&p, &q)| contains_midpoint(polygon, p, q))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: [Point; 4] = [(0, 0), (4, 0), (4, 4), (0, 4)];
+    const L_SHAPE: [Point; 6] = [(0, 0), (6, 0), (6, 2), (2, 2), (2, 6), (0, 6)];
+    const SLOT: [Point; 8] = [(0, 0), (0, 10), (1, 10), (1, 1), (2, 1), (2, 10), (3, 10), (3, 0)];
+
+    #[test]
+    fn measures() {
+        assert_eq!(32, doubled_signed_area(&SQUARE));
+        assert_eq!(-32, doubled_signed_area(&SQUARE.into_iter().rev().collect_vec()));
+        assert_eq!(16, area(&SQUARE));
+        assert_eq!(20, area(&L_SHAPE));
+        assert_eq!(24, rectilinear_perimeter(&L_SHAPE));
+        assert_eq!(12.0, perimeter(&[(0, 0), (4, 0), (0, 3)]));
+    }
+
+    #[test]
+    fn segments() {
+        assert!(on_segment((2, 2), (0, 0), (4, 4)));
+        assert!(on_segment((4, 4), (0, 0), (4, 4)));
+        assert!(!on_segment((5, 5), (0, 0), (4, 4)));
+        assert!(!on_segment((2, 3), (0, 0), (4, 4)));
+
+        assert!(segments_cross_properly((0, 0), (4, 4), (0, 4), (4, 0)));
+        assert!(!segments_cross_properly((0, 0), (4, 4), (2, 2), (4, 0)));
+        assert!(segments_intersect((0, 0), (4, 4), (2, 2), (4, 0)));
+        assert!(segments_intersect((0, 0), (4, 0), (4, 0), (8, 0)));
+        assert!(!segments_intersect((0, 0), (4, 0), (5, 0), (8, 0)));
+        assert!(!segments_intersect((0, 0), (4, 0), (0, 1), (4, 1)));
+    }
+
+    #[test]
+    fn point_location() {
+        let clockwise = SQUARE.into_iter().rev().collect_vec();
+
+        assert_eq!(1, winding_number(&SQUARE, (2, 2)));
+        assert_eq!(-1, winding_number(&clockwise, (2, 2)));
+        assert_eq!(0, winding_number(&SQUARE, (5, 2)));
+
+        assert_eq!(Containment::Inside, locate_point(&L_SHAPE, (1, 5)));
+        assert_eq!(Containment::Boundary, locate_point(&L_SHAPE, (2, 4)));
+        assert_eq!(Containment::Boundary, locate_point(&L_SHAPE, (6, 0)));
+        assert_eq!(Containment::Outside, locate_point(&L_SHAPE, (4, 4)));
+        assert!(contains(&L_SHAPE, (2, 2)));
+        assert!(!contains(&L_SHAPE, (3, 3)));
+    }
+
+    #[test]
+    fn rectangles() {
+        assert!(rectangle_inside_polygon(&L_SHAPE, (0, 0), (6, 2)));
+        assert!(rectangle_inside_polygon(&L_SHAPE, (2, 6), (0, 0)));
+        assert!(!rectangle_inside_polygon(&L_SHAPE, (0, 0), (6, 6)));
+        assert!(!rectangle_inside_polygon(&L_SHAPE, (0, 0), (3, 3)));
+        assert!(rectangle_inside_polygon(&SLOT, (0, 0), (1, 10)));
+        assert!(!rectangle_inside_polygon(&SLOT, (0, 0), (3, 10)));
+    }
+
+    #[test]
+    fn degenerate_rectangles_are_segments() {
+        assert!(rectangle_inside_polygon(&L_SHAPE, (2, 2), (6, 2)));
+        assert!(rectangle_inside_polygon(&L_SHAPE, (0, 6), (0, 0)));
+        assert!(!rectangle_inside_polygon(&L_SHAPE, (2, 2), (2, 7)));
+        assert!(segment_inside_polygon(&SLOT, (1, 1), (2, 1)));
+        assert!(!segment_inside_polygon(&SLOT, (0, 5), (3, 5)));
+        assert!(!segment_inside_polygon(&L_SHAPE, (1, 1), (5, 5)));
+    }
+}
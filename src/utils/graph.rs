@@ -0,0 +1,619 @@
+#![allow(dead_code)]
+
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use anyhow::{bail, Context, Result};
+use hashbrown::{HashMap, HashSet};
+use itertools::Itertools;
+
+use super::path_finding::Cost;
+use super::topological_sort::{kahn_topological_sort, Cycle};
+
+pub type NodeId = u32;
+
+const MAX_REQUIRED_NODES: usize = 16;
+
+#[derive(Clone, Debug)]
+pub struct Graph<N, W = ()> where N: Clone + Eq + Hash {
+    nodes: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    forward: Vec<Vec<(NodeId, W)>>,
+    reverse: Vec<Vec<(NodeId, W)>>,
+}
+
+impl<N, W> Default for Graph<N, W> where N: Clone + Eq + Hash {
+    fn default() -> Self {
+        Graph {
+            nodes: vec![],
+            ids: HashMap::new(),
+            forward: vec![],
+            reverse: vec![],
+        }
+    }
+}
+
+impl<N, W> Graph<N, W> where N: Clone + Eq + Hash {
+    pub fn new() -> Graph<N, W> {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.forward.iter().map(Vec::len).sum()
+    }
+
+    pub fn add_node(&mut self, node: N) -> NodeId {
+        if let Some(&id) = self.ids.get(&node) {
+            return id;
+        }
+
+        let id = NodeId::try_from(self.nodes.len()).expect("Too many nodes for a u32 node id");
+
+        self.ids.insert(node.clone(), id);
+        self.nodes.push(node);
+        self.forward.push(vec![]);
+        self.reverse.push(vec![]);
+
+        id
+    }
+
+    pub fn id(&self, node: &N) -> Option<NodeId> {
+        self.ids.get(node).copied()
+    }
+
+    pub fn node(&self, id: NodeId) -> &N {
+        &self.nodes[id as usize]
+    }
+
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId> + use<N, W> {
+        0..self.nodes.len() as NodeId
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &N)> {
+        self.nodes.iter().enumerate().map(|(id, node)| (id as NodeId, node))
+    }
+
+    pub fn successors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.forward[id as usize].iter().map(|&(successor, _)| successor)
+    }
+
+    pub fn predecessors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.reverse[id as usize].iter().map(|&(predecessor, _)| predecessor)
+    }
+
+    pub fn out_edges(&self, id: NodeId) -> &[(NodeId, W)] {
+        &self.forward[id as usize]
+    }
+
+    pub fn in_edges(&self, id: NodeId) -> &[(NodeId, W)] {
+        &self.reverse[id as usize]
+    }
+
+    pub fn out_degree(&self, id: NodeId) -> usize {
+        self.forward[id as usize].len()
+    }
+
+    pub fn in_degree(&self, id: NodeId) -> usize {
+        self.reverse[id as usize].len()
+    }
+
+    pub fn unit_neighbors(&self, id: NodeId) -> impl Iterator<Item = (NodeId, usize)> + '_ {
+        self.successors(id).map(|successor| (successor, 1))
+    }
+
+    pub fn unit_reverse_neighbors(&self, id: NodeId) -> impl Iterator<Item = (NodeId, usize)> + '_ {
+        self.predecessors(id).map(|predecessor| (predecessor, 1))
+    }
+
+    pub fn reachable_from(&self, source: NodeId) -> HashSet<NodeId> {
+        self.search(source, |id| self.successors(id), |_| true)
+    }
+
+    pub fn reaching(&self, target: NodeId) -> HashSet<NodeId> {
+        self.search(target, |id| self.predecessors(id), |_| true)
+    }
+
+    fn search<I>(&self, start: NodeId, next: impl Fn(NodeId) -> I, is_allowed: impl Fn(NodeId) -> bool) -> HashSet<NodeId> where
+        I: Iterator<Item = NodeId>,
+    {
+        if !is_allowed(start) {
+            return HashSet::new();
+        }
+
+        let mut seen = HashSet::from_iter([start]);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(current) = queue.pop_front() {
+            for neighbor in next(current) {
+                if is_allowed(neighbor) && seen.insert(neighbor) {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        seen
+    }
+
+    fn nodes_between(&self, source: NodeId, target: NodeId, forbidden: &[NodeId]) -> HashSet<NodeId> {
+        let is_allowed = |id| !forbidden.contains(&id);
+        let forward = self.search(source, |id| self.successors(id), is_allowed);
+        let backward = self.search(target, |id| self.predecessors(id), is_allowed);
+
+        forward.intersection(&backward).copied().collect()
+    }
+}
+
+impl<N, W> Graph<N, W> where N: Clone + Eq + Hash + Debug {
+    fn topological_order_within(&self, nodes: &HashSet<NodeId>) -> Result<Vec<NodeId>> {
+        match kahn_topological_sort(
+            nodes.iter().copied().sorted(),
+            |&id| self.successors(id).filter(|successor| nodes.contains(successor)),
+        ) {
+            Ok(order) => Ok(order),
+            Err(Cycle(cycle)) => bail!("{}", Cycle(cycle.into_iter().map(|id| self.node(id)).collect_vec())),
+        }
+    }
+
+    pub fn topological_order(&self) -> Result<Vec<NodeId>> {
+        self.topological_order_within(&self.node_ids().collect())
+    }
+
+    pub fn longest_path<C>(&self, source: NodeId, target: NodeId, weight: impl Fn(&W) -> C) -> Result<Option<(C, Vec<NodeId>)>> where
+        C: Cost,
+    {
+        let nodes = self.nodes_between(source, target, &[]);
+        let order = self.topological_order_within(&nodes)?;
+
+        let longest = self.longest_paths_in_order(&order, &nodes, [source], weight);
+
+        Ok(longest.get(&target).map(|&(length, _)| (length, Self::trace_longest_path(&longest, target))))
+    }
+
+    pub fn longest_path_by_edges(&self, source: NodeId, target: NodeId) -> Result<Option<(usize, Vec<NodeId>)>> {
+        self.longest_path(source, target, |_| 1)
+    }
+
+    pub fn critical_path<C>(&self, weight: impl Fn(&W) -> C) -> Result<Option<(C, Vec<NodeId>)>> where
+        C: Cost,
+    {
+        let nodes: HashSet<NodeId> = self.node_ids().collect();
+        let order = self.topological_order_within(&nodes)?;
+        let longest = self.longest_paths_in_order(&order, &nodes, order.iter().copied(), weight);
+
+        let Some((&end, &(length, _))) = longest.iter().max_by_key(|&(&id, &(length, _))| (length, Reverse(id))) else {
+            return Ok(None);
+        };
+
+        Ok(Some((length, Self::trace_longest_path(&longest, end))))
+    }
+
+    fn longest_paths_in_order<C>(
+        &self,
+        order: &[NodeId],
+        nodes: &HashSet<NodeId>,
+        sources: impl IntoIterator<Item = NodeId>,
+        weight: impl Fn(&W) -> C,
+    ) -> HashMap<NodeId, (C, Option<NodeId>)> where
+        C: Cost,
+    {
+        let mut longest: HashMap<NodeId, (C, Option<NodeId>)> = sources
+            .into_iter()
+            .filter(|source| nodes.contains(source))
+            .map(|source| (source, (C::default(), None)))
+            .collect();
+
+        for &current in order {
+            let Some(&(length, _)) = longest.get(&current) else { continue; };
+
+            for (successor, edge_weight) in self.out_edges(current).iter().filter(|(successor, _)| nodes.contains(successor)) {
+                let candidate = length + weight(edge_weight);
+
+                if longest.get(successor).is_none_or(|&(best, _)| candidate > best) {
+                    longest.insert(*successor, (candidate, Some(current)));
+                }
+            }
+        }
+
+        longest
+    }
+
+    fn trace_longest_path<C>(longest: &HashMap<NodeId, (C, Option<NodeId>)>, end: NodeId) -> Vec<NodeId> {
+        let mut path = vec![end];
+
+        while let Some(&(_, Some(previous))) = longest.get(path.last().unwrap()) {
+            path.push(previous);
+        }
+
+        path.reverse();
+        path
+    }
+
+    pub fn paths_through_nodes(&self, source: NodeId, target: NodeId) -> Result<HashMap<NodeId, u128>> {
+        let nodes = self.nodes_between(source, target, &[]);
+        let order = self.topological_order_within(&nodes)?;
+
+        let mut paths_from_source: HashMap<NodeId, u128> = HashMap::new();
+        let mut paths_to_target: HashMap<NodeId, u128> = HashMap::new();
+
+        if nodes.contains(&source) {
+            paths_from_source.insert(source, 1);
+            paths_to_target.insert(target, 1);
+        }
+
+        for &current in &order {
+            let current_paths = paths_from_source.get(&current).copied().unwrap_or_default();
+
+            for successor in self.successors(current).filter(|successor| nodes.contains(successor)) {
+                let entry = paths_from_source.entry(successor).or_default();
+                *entry = entry.checked_add(current_paths).context("Path count overflow")?;
+            }
+        }
+
+        for &current in order.iter().rev() {
+            let current_paths = paths_to_target.get(&current).copied().unwrap_or_default();
+
+            for predecessor in self.predecessors(current).filter(|predecessor| nodes.contains(predecessor)) {
+                let entry = paths_to_target.entry(predecessor).or_default();
+                *entry = entry.checked_add(current_paths).context("Path count overflow")?;
+            }
+        }
+
+        nodes
+            .iter()
+            .map(|&id| {
+                let through = paths_from_source[&id]
+                    .checked_mul(paths_to_target[&id])
+                    .with_context(|| format!("Path count overflow at {:?}", self.node(id)))?;
+
+                Ok((id, through))
+            })
+            .try_collect()
+    }
+
+    pub fn count_paths(&self, source: NodeId, target: NodeId, required: &[NodeId], forbidden: &[NodeId]) -> Result<u128> {
+        if required.len() > MAX_REQUIRED_NODES {
+            bail!("Path counting supports at most {MAX_REQUIRED_NODES} required nodes, got {}", required.len());
+        }
+
+        let nodes = self.nodes_between(source, target, forbidden);
+        let order = self.topological_order_within(&nodes)?;
+
+        let required_mask = |id: NodeId| -> usize {
+            required
+                .iter()
+                .positions(|&required_id| required_id == id)
+                .fold(0, |mask, index| mask | 1 << index)
+        };
+
+        let full_mask = (1 << required.len()) - 1;
+        let mut num_paths: HashMap<NodeId, Vec<u128>> = HashMap::new();
+
+        if nodes.contains(&source) {
+            num_paths.entry(source).or_insert_with(|| vec![0; full_mask + 1])[required_mask(source)] = 1;
+        }
+
+        for current in order {
+            let Some(current_num_paths) = num_paths.get(&current).cloned() else { continue; };
+
+            for successor in self.successors(current).filter(|successor| nodes.contains(successor)) {
+                let successor_mask = required_mask(successor);
+                let successor_num_paths = num_paths.entry(successor).or_insert_with(|| vec![0; full_mask + 1]);
+
+                for (mask, &count) in current_num_paths.iter().enumerate().filter(|&(_, &count)| count > 0) {
+                    let entry = &mut successor_num_paths[mask | successor_mask];
+                    *entry = entry
+                        .checked_add(count)
+                        .with_context(|| format!("Path count overflow at {:?}", self.node(successor)))?;
+                }
+            }
+        }
+
+        Ok(num_paths.get(&target).map(|counts| counts[full_mask]).unwrap_or_default())
+    }
+}
+
+impl<N, W> Graph<N, W> where N: Clone + Eq + Hash, W: Clone {
+    pub fn add_edge(&mut self, from: N, to: N, weight: W) -> (NodeId, NodeId) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        self.add_edge_by_id(from, to, weight);
+
+        (from, to)
+    }
+
+    pub fn add_edge_by_id(&mut self, from: NodeId, to: NodeId, weight: W) {
+        self.forward[from as usize].push((to, weight.clone()));
+        self.reverse[to as usize].push((from, weight));
+    }
+
+    pub fn add_undirected_edge(&mut self, a: N, b: N, weight: W) -> (NodeId, NodeId) {
+        let (a, b) = self.add_edge(a, b, weight.clone());
+        self.add_edge_by_id(b, a, weight);
+
+        (a, b)
+    }
+
+    pub fn from_edges(edges: impl IntoIterator<Item = (N, N, W)>) -> Graph<N, W> {
+        let mut graph = Graph::new();
+
+        for (from, to, weight) in edges {
+            graph.add_edge(from, to, weight);
+        }
+
+        graph
+    }
+
+    pub fn from_undirected_edges(edges: impl IntoIterator<Item = (N, N, W)>) -> Graph<N, W> {
+        let mut graph = Graph::new();
+
+        for (a, b, weight) in edges {
+            graph.add_undirected_edge(a, b, weight);
+        }
+
+        graph
+    }
+
+    pub fn reversed(&self) -> Graph<N, W> {
+        Graph {
+            nodes: self.nodes.clone(),
+            ids: self.ids.clone(),
+            forward: self.reverse.clone(),
+            reverse: self.forward.clone(),
+        }
+    }
+}
+
+impl<N, W> Graph<N, W> where N: Clone + Eq + Hash, W: Copy {
+    pub fn neighbors(&self, id: NodeId) -> impl Iterator<Item = (NodeId, W)> + '_ {
+        self.forward[id as usize].iter().copied()
+    }
+
+    pub fn reverse_neighbors(&self, id: NodeId) -> impl Iterator<Item = (NodeId, W)> + '_ {
+        self.reverse[id as usize].iter().copied()
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (NodeId, NodeId, W)> + '_ {
+        self.forward
+            .iter()
+            .enumerate()
+            .flat_map(|(from, edges)| edges.iter().map(move |&(to, weight)| (from as NodeId, to, weight)))
+    }
+}
+
+impl Graph<String> {
+    pub fn parse_adjacency_list(input: &str) -> Result<Graph<String>> {
+        let mut graph = Graph::new();
+
+        for line in input.lines() {
+            let (node, successors) = line.split_once(':').with_context(|| format!("Invalid adjacency line: {line}"))?;
+            let node = graph.add_node(node.trim().to_string());
+
+            for successor in successors.split_ascii_whitespace() {
+                let successor = graph.add_node(successor.to_string());
+                graph.add_edge_by_id(node, successor, ());
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::random::xorshift;
+
+    fn random_dags(seed: u64) -> impl Iterator<Item = Graph<usize, u64>> {
+        let mut random = xorshift(seed);
+
+        (0..200).map(move |_| {
+            let len = (random() % 7 + 1) as usize;
+            let mut graph = Graph::new();
+
+            for node in 0..len {
+                graph.add_node(node);
+            }
+
+            for (from, to) in (0..len).tuple_combinations() {
+                for _ in 0..random() % 4 / 2 + random() % 4 / 3 {
+                    graph.add_edge(from, to, random() % 10);
+                }
+            }
+
+            graph
+        })
+    }
+
+    fn all_paths<W>(graph: &Graph<usize, W>, source: NodeId, target: NodeId) -> Vec<Vec<(NodeId, Option<&W>)>> where
+        W: Clone,
+    {
+        if source == target {
+            return vec![vec![(source, None)]];
+        }
+
+        graph
+            .out_edges(source)
+            .iter()
+            .flat_map(|(successor, weight)| {
+                all_paths(graph, *successor, target).into_iter().map(move |mut path| {
+                    path[0].1 = Some(weight);
+                    path.insert(0, (source, None));
+                    path
+                })
+            })
+            .collect()
+    }
+
+    fn node_pairs<N, W>(graph: &Graph<N, W>) -> impl Iterator<Item = (NodeId, NodeId)> where N: Clone + Eq + Hash {
+        let len = graph.len() as NodeId;
+
+        (0..len).cartesian_product(0..len)
+    }
+
+    fn path_weight(path: &[(NodeId, Option<&u64>)]) -> u64 {
+        path.iter().filter_map(|&(_, weight)| weight).sum()
+    }
+
+    fn heaviest_edges(graph: &Graph<usize, u64>, path: &[NodeId]) -> Option<u64> {
+        path.iter()
+            .tuple_windows()
+            .map(|(&from, &to)| graph.out_edges(from).iter().filter(|&&(successor, _)| successor == to).map(|&(_, weight)| weight).max())
+            .sum()
+    }
+
+    fn cyclic() -> Graph<char, u64> {
+        Graph::from_edges([('a', 'b', 1), ('b', 'c', 1), ('c', 'b', 1), ('c', 'd', 1)])
+    }
+
+    #[test]
+    fn critical_path_of_a_task_graph() {
+        let graph = Graph::from_edges([
+            ("start", "design", 3u64),
+            ("design", "build", 5),
+            ("design", "docs", 2),
+            ("start", "order", 9),
+            ("order", "build", 1),
+            ("build", "ship", 4),
+            ("docs", "ship", 1),
+        ]);
+
+        let (length, path) = graph.critical_path(|&weight| weight).unwrap().unwrap();
+        let path = path.into_iter().map(|id| *graph.node(id)).collect_vec();
+
+        assert_eq!(14, length);
+        assert_eq!(vec!["start", "order", "build", "ship"], path);
+        assert_eq!(None, Graph::<char, u64>::new().critical_path(|&weight| weight).unwrap());
+    }
+
+    #[test]
+    fn cycles_are_errors() {
+        let graph = cyclic();
+        let (a, d) = (graph.id(&'a').unwrap(), graph.id(&'d').unwrap());
+
+        assert!(graph.longest_path(a, d, |&weight| weight).is_err());
+        assert!(graph.longest_path_by_edges(a, d).is_err());
+        assert!(graph.critical_path(|&weight| weight).is_err());
+        assert!(graph.paths_through_nodes(a, d).is_err());
+        assert_eq!(None, graph.longest_path_by_edges(d, a).unwrap());
+    }
+
+    #[test]
+    fn longest_paths_match_brute_force() {
+        for graph in random_dags(41) {
+            for (source, target) in node_pairs(&graph) {
+                let paths = all_paths(&graph, source, target);
+
+                let longest = graph.longest_path(source, target, |&weight| weight).unwrap();
+                assert_eq!(paths.iter().map(|path| path_weight(path)).max(), longest.as_ref().map(|&(length, _)| length));
+
+                if let Some((length, path)) = longest {
+                    assert_eq!((source, target), (path[0], path[path.len() - 1]));
+                    assert_eq!(Some(length), heaviest_edges(&graph, &path));
+                }
+
+                let longest = graph.longest_path_by_edges(source, target).unwrap();
+                assert_eq!(paths.iter().map(|path| path.len() - 1).max(), longest.as_ref().map(|&(length, _)| length));
+
+                if let Some((length, path)) = longest {
+                    assert_eq!((source, target), (path[0], path[path.len() - 1]));
+                    assert_eq!(length + 1, path.len());
+                    assert!(path.iter().tuple_windows().all(|(&from, &to)| graph.successors(from).contains(&to)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn critical_path_matches_brute_force() {
+        for graph in random_dags(43) {
+            let expected = node_pairs(&graph)
+                .flat_map(|(source, target)| all_paths(&graph, source, target))
+                .map(|path| path_weight(&path))
+                .max();
+
+            let (length, path) = graph.critical_path(|&weight| weight).unwrap().unwrap();
+
+            assert_eq!(expected, Some(length));
+            assert_eq!(Some(length), heaviest_edges(&graph, &path));
+        }
+    }
+
+    #[test]
+    fn count_paths_visits_required_nodes_in_any_order() {
+        let graph = Graph::from_edges([('s', 'a', ()), ('s', 'b', ()), ('a', 'b', ()), ('a', 't', ()), ('b', 't', ())]);
+        let id = |node| graph.id(&node).unwrap();
+
+        assert_eq!(3, graph.count_paths(id('s'), id('t'), &[], &[]).unwrap());
+        assert_eq!(1, graph.count_paths(id('s'), id('t'), &[id('b'), id('a')], &[]).unwrap());
+        assert_eq!(1, graph.count_paths(id('s'), id('t'), &[id('a'), id('b')], &[]).unwrap());
+        assert_eq!(2, graph.count_paths(id('s'), id('t'), &[id('b')], &[]).unwrap());
+        assert_eq!(1, graph.count_paths(id('s'), id('t'), &[], &[id('b')]).unwrap());
+        assert_eq!(0, graph.count_paths(id('s'), id('t'), &[id('b')], &[id('b')]).unwrap());
+        assert_eq!(0, graph.count_paths(id('s'), id('t'), &[], &[id('s')]).unwrap());
+    }
+
+    #[test]
+    fn count_paths_rejects_cycles_and_too_many_required_nodes() {
+        let graph = cyclic();
+        let (a, d) = (graph.id(&'a').unwrap(), graph.id(&'d').unwrap());
+
+        assert!(graph.count_paths(a, d, &[], &[]).is_err());
+        assert_eq!(0, graph.count_paths(a, d, &[], &[graph.id(&'c').unwrap()]).unwrap());
+
+        let line = Graph::from_edges((0..MAX_REQUIRED_NODES + 1).map(|node| (node, node + 1, ())));
+        let required = line.node_ids().collect_vec();
+        let last = required[required.len() - 1];
+
+        assert_eq!(1, line.count_paths(0, last, &required[..MAX_REQUIRED_NODES], &[]).unwrap());
+        assert!(line.count_paths(0, last, &required, &[]).is_err());
+    }
+
+    #[test]
+    fn count_paths_matches_brute_force() {
+        let mut random = xorshift(53);
+
+        for graph in random_dags(59) {
+            for (source, target) in node_pairs(&graph) {
+                let mut pick = || graph.node_ids().filter(|_| random().is_multiple_of(4)).collect_vec();
+                let (required, forbidden) = (pick(), pick());
+
+                let expected = all_paths(&graph, source, target)
+                    .iter()
+                    .map(|path| path.iter().map(|&(id, _)| id).collect_vec())
+                    .filter(|path| required.iter().all(|id| path.contains(id)))
+                    .filter(|path| !forbidden.iter().any(|id| path.contains(id)))
+                    .count() as u128;
+
+                assert_eq!(expected, graph.count_paths(source, target, &required, &forbidden).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn paths_through_nodes_match_brute_force() {
+        for graph in random_dags(47) {
+            for (source, target) in node_pairs(&graph) {
+                let paths = all_paths(&graph, source, target);
+                let expected: HashMap<NodeId, u128> = paths
+                    .iter()
+                    .flat_map(|path| path.iter().map(|&(id, _)| id).unique())
+                    .counts()
+                    .into_iter()
+                    .map(|(id, count)| (id, count as u128))
+                    .collect();
+
+                assert_eq!(expected, graph.paths_through_nodes(source, target).unwrap());
+            }
+        }
+    }
+}
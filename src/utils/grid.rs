@@ -5,7 +5,7 @@ use std::collections::HashMap;
 use std::fmt::{Debug, Display};
 use std::ops::{Index, IndexMut};
 
-use anyhow::{bail, Context, Error, Result};
+use anyhow::{bail, Error, Result};
 use derive_more::derive::Display;
 use itertools::Itertools;
 use strum::EnumIter;
@@ -87,6 +87,65 @@ impl Position {
             Position(i + 1, j + 1),
         ]
     }
+
+    pub fn neighbors(&self, neighborhood: Neighborhood) -> impl Iterator<Item = Position> + use<> {
+        let Position(i, j) = *self;
+
+        neighborhood
+            .offsets()
+            .into_iter()
+            .map(move |(di, dj)| Position(i + di, j + dj))
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Neighborhood {
+    VonNeumann,
+    Moore,
+    VonNeumannRadius(usize),
+    MooreRadius(usize),
+}
+
+impl Neighborhood {
+    pub fn offsets(self) -> Vec<(isize, isize)> {
+        let (radius, max_manhattan) = match self {
+            Neighborhood::VonNeumann => (1, 1),
+            Neighborhood::Moore => (1, 2),
+            Neighborhood::VonNeumannRadius(radius) => (radius as isize, radius as isize),
+            Neighborhood::MooreRadius(radius) => (radius as isize, 2 * radius as isize),
+        };
+
+        (-radius..=radius)
+            .cartesian_product(-radius..=radius)
+            .filter(|&(di, dj)| (di, dj) != (0, 0) && di.abs() + dj.abs() <= max_manhattan)
+            .collect()
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Hash)]
+pub enum Topology {
+    #[default]
+    Bounded,
+    Toroidal,
+    Reflecting,
+}
+
+impl Topology {
+    fn resolve_coordinate(self, x: isize, size: isize) -> Option<isize> {
+        if size <= 0 {
+            return None;
+        }
+
+        match self {
+            Topology::Bounded => (0..size).contains(&x).then_some(x),
+            Topology::Toroidal => Some(x.rem_euclid(size)),
+            Topology::Reflecting if size == 1 => Some(0),
+            Topology::Reflecting => {
+                let x = x.rem_euclid(2 * (size - 1));
+                Some(if x < size { x } else { 2 * (size - 1) - x })
+            }
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -95,6 +154,7 @@ pub struct Grid<T> where T: Clone {
     rows: GridSize,
     cols: GridSize,
     len: GridSize,
+    topology: Topology,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display)]
@@ -160,6 +220,7 @@ impl<T> Grid<T> where T: Clone {
             rows: rows.into(),
             cols: cols.into(),
             len: len.into(),
+            topology: Topology::default(),
         }
     }
 
@@ -181,6 +242,7 @@ impl<T> Grid<T> where T: Clone {
             rows: rows.into(),
             cols: cols.into(),
             len: len.into(),
+            topology: Topology::default(),
         }
     }
 
@@ -219,6 +281,37 @@ impl<T> Grid<T> where T: Clone {
     pub fn set(&mut self, &Position(i, j): &Position, value: T) {
         self[(i, j)] = value;
     }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
+
+    pub fn with_topology(mut self, topology: Topology) -> Grid<T> {
+        self.topology = topology;
+        self
+    }
+
+    pub fn resolve(&self, &Position(i, j): &Position) -> Option<Position> {
+        Some(Position(
+            self.topology.resolve_coordinate(i, self.rows())?,
+            self.topology.resolve_coordinate(j, self.cols())?,
+        ))
+    }
+
+    pub fn neighbors(&self, position: &Position, neighborhood: Neighborhood) -> impl Iterator<Item = (Position, &T)> {
+        let position = *position;
+
+        position
+            .neighbors(neighborhood)
+            .filter_map(|neighbor| self.resolve(&neighbor))
+            .filter(move |&neighbor| neighbor != position)
+            .unique()
+            .map(|neighbor| (neighbor, &self[(neighbor.0, neighbor.1)]))
+    }
 }
 
 impl<T, S> Index<(S, S)> for Grid<T> where T: Clone, S: Into<GridSize> {
@@ -307,36 +400,382 @@ impl<'a, T> IntoIterator for &'a Grid<T> where T: Clone {
     }
 }
 
+impl<T> Grid<T> where T: Clone {
+    fn from_fn<S, F>(rows: S, cols: S, f: F) -> Grid<T> where
+        S: Into<GridSize>,
+        F: Fn(isize, isize) -> T,
+    {
+        let rows: isize = rows.into().into();
+        let cols: isize = cols.into().into();
+        let values = (0..rows).flat_map(|i| (0..cols).map(move |j| (i, j))).map(|(i, j)| f(i, j));
+
+        Grid::from(rows, cols, values)
+    }
+
+    pub fn transpose(&self) -> Grid<T> {
+        Grid::from_fn(self.cols, self.rows, |i, j| self[(j, i)].clone())
+            .with_topology(self.topology)
+    }
+
+    pub fn rotate_cw(&self) -> Grid<T> {
+        let rows: isize = self.rows();
+        Grid::from_fn(self.cols, self.rows, |i, j| self[(rows - 1 - j, i)].clone())
+            .with_topology(self.topology)
+    }
+
+    pub fn rotate_ccw(&self) -> Grid<T> {
+        let cols: isize = self.cols();
+        Grid::from_fn(self.cols, self.rows, |i, j| self[(j, cols - 1 - i)].clone())
+            .with_topology(self.topology)
+    }
+
+    pub fn flip_h(&self) -> Grid<T> {
+        let cols: isize = self.cols();
+        Grid::from_fn(self.rows, self.cols, |i, j| self[(i, cols - 1 - j)].clone())
+            .with_topology(self.topology)
+    }
+
+    pub fn flip_v(&self) -> Grid<T> {
+        let rows: isize = self.rows();
+        Grid::from_fn(self.rows, self.cols, |i, j| self[(rows - 1 - i, j)].clone())
+            .with_topology(self.topology)
+    }
+
+    pub fn sub_grid(&self, origin: Position, rows: usize, cols: usize) -> Option<SubGrid<'_, T>> {
+        let Position(i, j) = origin;
+
+        if i < 0 || j < 0 || self.rows < i + rows as isize || self.cols < j + cols as isize {
+            return None;
+        }
+
+        Some(SubGrid {
+            grid: self,
+            origin,
+            rows: rows.into(),
+            cols: cols.into(),
+        })
+    }
+
+    pub fn crop(&self, origin: Position, rows: usize, cols: usize) -> Option<Grid<T>> {
+        self.sub_grid(origin, rows, cols).map(|sub_grid| sub_grid.to_grid())
+    }
+
+    pub fn windows(&self, rows: usize, cols: usize) -> impl Iterator<Item = SubGrid<'_, T>> {
+        let num_rows = (self.rows::<usize>() + 1).saturating_sub(rows);
+        let num_cols = (self.cols::<usize>() + 1).saturating_sub(cols);
+
+        (0..num_rows)
+            .cartesian_product(0..num_cols)
+            .filter_map(move |(i, j)| self.sub_grid(Position::new(i, j), rows, cols))
+    }
+}
+
+pub struct SubGrid<'a, T> where T: Clone {
+    grid: &'a Grid<T>,
+    origin: Position,
+    rows: GridSize,
+    cols: GridSize,
+}
+
+impl<T> Clone for SubGrid<'_, T> where T: Clone {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SubGrid<'_, T> where T: Clone {}
+
+impl<'a, T> SubGrid<'a, T> where T: Clone {
+    pub fn dimensions<S>(&self) -> (S, S) where S: From<GridSize> {
+        (self.rows(), self.cols())
+    }
+
+    pub fn rows<S>(&self) -> S where S: From<GridSize> {
+        S::from(self.rows)
+    }
+
+    pub fn cols<S>(&self) -> S where S: From<GridSize> {
+        S::from(self.cols)
+    }
+
+    pub fn origin(&self) -> Position {
+        self.origin
+    }
+
+    pub fn get(&self, &Position(i, j): &Position) -> Option<&'a T> {
+        if i < 0 || i >= self.rows.into() || j < 0 || j >= self.cols.into() {
+            return None;
+        }
+
+        self.grid.get(&self.parent_position(&Position(i, j)))
+    }
+
+    pub fn parent_position(&self, &Position(i, j): &Position) -> Position {
+        Position(self.origin.0 + i, self.origin.1 + j)
+    }
+
+    pub fn local_position(&self, &Position(i, j): &Position) -> Option<Position> {
+        let position = Position(i - self.origin.0, j - self.origin.1);
+        self.get(&position).map(|_| position)
+    }
+
+    pub fn to_grid(self) -> Grid<T> {
+        Grid::from_fn(self.rows, self.cols, |i, j| {
+            self.grid[(self.origin.0 + i, self.origin.1 + j)].clone()
+        })
+    }
+}
+
+pub struct SubGridIter<'a, T> where T: Clone {
+    sub_grid: SubGrid<'a, T>,
+    index: usize,
+}
+
+impl<'a, T> Iterator for SubGridIter<'a, T> where T: Clone {
+    type Item = (Position, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.sub_grid.rows::<usize>() * self.sub_grid.cols::<usize>() {
+            return None;
+        }
+
+        let index = self.index as isize;
+        self.index += 1;
+        let position = Position(index / self.sub_grid.cols::<isize>(), index % self.sub_grid.cols::<isize>());
+        Some((position, self.sub_grid.get(&position)?))
+    }
+}
+
+impl<'a, T> IntoIterator for SubGrid<'a, T> where T: Clone {
+    type Item = (Position, &'a T);
+    type IntoIter = SubGridIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        SubGridIter {
+            sub_grid: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &SubGrid<'a, T> where T: Clone {
+    type Item = (Position, &'a T);
+    type IntoIter = SubGridIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        SubGridIter {
+            sub_grid: *self,
+            index: 0,
+        }
+    }
+}
+
+pub trait ToChar {
+    fn to_char(&self) -> char;
+}
+
+impl ToChar for char {
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+impl ToChar for bool {
+    fn to_char(&self) -> char {
+        if *self { '#' } else { '.' }
+    }
+}
+
+impl<T> Display for Grid<T> where T: Clone + ToChar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.render().fmt(f)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, EnumIter)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    fn ansi_code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Highlight {
+    Char(char),
+    Color(Color),
+    ColoredChar(char, Color),
+}
+
+pub struct GridRenderer<'a, T> where T: Clone {
+    grid: &'a Grid<T>,
+    highlights: HashMap<Position, Highlight>,
+}
+
+impl<T> Grid<T> where T: Clone {
+    pub fn render(&self) -> GridRenderer<'_, T> {
+        GridRenderer {
+            grid: self,
+            highlights: HashMap::new(),
+        }
+    }
+}
+
+impl<T> GridRenderer<'_, T> where T: Clone {
+    pub fn highlight<I>(mut self, positions: I, highlight: Highlight) -> Self where I: IntoIterator<Item = Position> {
+        self.highlights.extend(positions.into_iter().map(|position| (position, highlight)));
+        self
+    }
+}
+
+impl<T> Display for GridRenderer<'_, T> where T: Clone + ToChar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for i in 0..self.grid.rows::<isize>() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            for j in 0..self.grid.cols::<isize>() {
+                let c = self.grid[(i, j)].to_char();
+
+                match self.highlights.get(&Position(i, j)) {
+                    None => write!(f, "{c}")?,
+                    Some(Highlight::Char(c)) => write!(f, "{c}")?,
+                    Some(Highlight::Color(color)) => write!(f, "\x1b[{}m{c}\x1b[0m", color.ansi_code())?,
+                    Some(Highlight::ColoredChar(c, color)) => write!(f, "\x1b[{}m{c}\x1b[0m", color.ansi_code())?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 type MarkerPositions = HashMap<char, Vec<Position>>;
 
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum GridParseError {
+    NoLines,
+    InvalidCharacter { row: usize, col: usize, character: char, reason: String, line: String },
+    RaggedLine { row: usize, width: usize, expected_width: usize, line: String },
+}
+
+impl GridParseError {
+    fn snippet(row: usize, col: usize, line: &str) -> String {
+        let line_number = (row + 1).to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        format!("{gutter} |\n{line_number} | {line}\n{gutter} | {}^", " ".repeat(col))
+    }
+}
+
+impl Display for GridParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridParseError::NoLines => write!(f, "No input lines found"),
+            GridParseError::InvalidCharacter { row, col, character, reason, line } => write!(
+                f,
+                "Unable to parse character {character:?} at line {}, column {}: {reason}\n{}",
+                row + 1,
+                col + 1,
+                Self::snippet(*row, *col, line),
+            ),
+            GridParseError::RaggedLine { row, width, expected_width, line } => write!(
+                f,
+                "Line {} has {width} columns, expected {expected_width}\n{}",
+                row + 1,
+                Self::snippet(*row, *width.min(expected_width), line),
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridParseError {}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GridParseOptions<T> {
+    pub pad_with: Option<T>,
+    pub trim_trailing_whitespace: bool,
+}
+
+impl<T> Default for GridParseOptions<T> {
+    fn default() -> Self {
+        GridParseOptions {
+            pad_with: None,
+            trim_trailing_whitespace: false,
+        }
+    }
+}
+
 impl<T> Grid<T> where
     T: Copy + Clone + TryFrom<char>,
-    Result<T, <T as TryFrom<char>>::Error>: Context<T, <T as TryFrom<char>>::Error>,
+    <T as TryFrom<char>>::Error: Display,
 {
-    pub fn parse_with_position_detection(input: &str, markers: &[char], replacement: Option<T>) -> Result<(Grid<T>, MarkerPositions)> {
-        use anyhow::Ok;
+    pub fn parse_with_options(
+        input: &str,
+        markers: &[char],
+        replacement: Option<T>,
+        options: GridParseOptions<T>,
+    ) -> std::result::Result<(Grid<T>, MarkerPositions), GridParseError> {
+        let lines = input
+            .lines()
+            .map(|line| if options.trim_trailing_whitespace { line.trim_end() } else { line })
+            .collect_vec();
 
-        let rows = input.lines().count();
-        let cols = input.lines().next().context("No input lines found")?.len();
+        let rows = lines.len();
+        let cols = match options.pad_with {
+            Some(_) => lines.iter().map(|line| line.chars().count()).max(),
+            None => lines.first().map(|line| line.chars().count()),
+        }.ok_or(GridParseError::NoLines)?;
 
-        let (ts, ps) = input
-            .lines()
+        for (i, line) in lines.iter().enumerate() {
+            let width = line.chars().count();
+
+            if width > cols || (width < cols && options.pad_with.is_none()) {
+                return Err(GridParseError::RaggedLine { row: i, width, expected_width: cols, line: line.to_string() });
+            }
+        }
+
+        let (ts, ps) = lines
+            .iter()
             .enumerate()
             .flat_map(|(i, line)| line
                 .chars()
                 .enumerate()
                 .map(move |(j, c)| {
-                    if markers.contains(&c) && let Some(replacement_tile) = replacement {
-                        Ok((replacement_tile, Some((c, Position(i as isize, j as isize)))))
-                    } else if markers.contains(&c) {
-                        Ok((
-                            T::try_from(c).context(format!("Unable to parse character: {c}"))?,
-                            Some((c, Position(i as isize, j as isize))),
-                        ))
-                    } else {
-                        Ok((T::try_from(c).context(format!("Unable to parse character: {c}"))?, None))
+                    let marker = markers.contains(&c).then_some((c, Position::new(i, j)));
+
+                    if let Some(replacement_tile) = replacement && marker.is_some() {
+                        return Ok((replacement_tile, marker));
                     }
+
+                    T::try_from(c)
+                        .map(|tile| (tile, marker))
+                        .map_err(|error| GridParseError::InvalidCharacter {
+                            row: i,
+                            col: j,
+                            character: c,
+                            reason: error.to_string(),
+                            line: line.to_string(),
+                        })
                 })
+                .chain(options.pad_with.into_iter().cycle().map(|tile| Ok((tile, None))))
+                .take(cols)
             )
             .process_results(|iter| iter.unzip::<_, _, Vec<_>, Vec<_>>())?;
 
@@ -345,8 +784,171 @@ impl<T> Grid<T> where
         Ok((Grid::from(rows, cols, ts), positions))
     }
 
+    pub fn parse_with_position_detection(input: &str, markers: &[char], replacement: Option<T>) -> Result<(Grid<T>, MarkerPositions)> {
+        Ok(Self::parse_with_options(input, markers, replacement, GridParseOptions::default())?)
+    }
+
     pub fn parse(input: &str) -> Result<Grid<T>> {
         Self::parse_with_position_detection(input, &[], None)
             .map(|(grid, _)| grid)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn neighbor_positions(grid: &Grid<u8>, position: Position, neighborhood: Neighborhood) -> Vec<Position> {
+        grid.neighbors(&position, neighborhood).map(|(neighbor, _)| neighbor).sorted().collect()
+    }
+
+    #[test]
+    fn reflecting_reflects_across_the_edge() {
+        let grid = Grid::new_with_value(3usize, 3usize, 0u8).with_topology(Topology::Reflecting);
+
+        assert_eq!(Some(Position(1, 1)), grid.resolve(&Position(-1, -1)));
+        assert_eq!(Some(Position(1, 0)), grid.resolve(&Position(3, 0)));
+        assert_eq!(
+            vec![Position(0, 1), Position(1, 0), Position(1, 1)],
+            neighbor_positions(&grid, Position(0, 0), Neighborhood::Moore),
+        );
+    }
+
+    #[test]
+    fn toroidal_neighbors_exclude_self_and_duplicates() {
+        let grid = Grid::new_with_value(1usize, 2usize, 0u8).with_topology(Topology::Toroidal);
+
+        assert_eq!(vec![Position(0, 1)], neighbor_positions(&grid, Position(0, 0), Neighborhood::Moore));
+        assert_eq!(vec![Position(0, 0)], neighbor_positions(&grid, Position(0, 1), Neighborhood::VonNeumann));
+    }
+
+    #[test]
+    fn bounded_neighbors_stay_inside() {
+        let grid = Grid::new_with_value(3usize, 3usize, 0u8);
+
+        assert_eq!(
+            vec![Position(0, 1), Position(1, 0)],
+            neighbor_positions(&grid, Position(0, 0), Neighborhood::VonNeumann),
+        );
+        assert_eq!(8, neighbor_positions(&grid, Position(1, 1), Neighborhood::Moore).len());
+    }
+
+    fn fixture() -> Grid<char> {
+        Grid::parse("abc\ndef\n").unwrap()
+    }
+
+    #[test]
+    fn transforms_non_square_grids() {
+        let grid = fixture();
+
+        assert_eq!("ad\nbe\ncf", grid.transpose().to_string());
+        assert_eq!("da\neb\nfc", grid.rotate_cw().to_string());
+        assert_eq!("cf\nbe\nad", grid.rotate_ccw().to_string());
+        assert_eq!("cba\nfed", grid.flip_h().to_string());
+        assert_eq!("def\nabc", grid.flip_v().to_string());
+        assert_eq!((3, 2), grid.rotate_cw().dimensions::<usize>());
+        assert_eq!(grid.to_string(), grid.rotate_cw().rotate_cw().rotate_cw().rotate_cw().to_string());
+        assert_eq!(grid.to_string(), grid.rotate_cw().rotate_ccw().to_string());
+        assert_eq!(Topology::Toroidal, grid.with_topology(Topology::Toroidal).rotate_ccw().topology());
+    }
+
+    #[test]
+    fn sub_grids_map_between_local_and_parent_positions() {
+        let grid = fixture();
+        let sub_grid = grid.sub_grid(Position(0, 1), 2, 2).unwrap();
+
+        assert_eq!((2, 2), sub_grid.dimensions::<usize>());
+        assert_eq!("bc\nef", sub_grid.to_grid().to_string());
+        assert_eq!(Some(&'f'), sub_grid.get(&Position(1, 1)));
+        assert_eq!(None, sub_grid.get(&Position(0, 2)));
+        assert_eq!(Position(1, 1), sub_grid.parent_position(&Position(1, 0)));
+        assert_eq!(Some(Position(1, 1)), sub_grid.local_position(&Position(1, 2)));
+        assert_eq!(None, sub_grid.local_position(&Position(0, 0)));
+        assert_eq!(
+            vec![(Position(0, 0), 'b'), (Position(0, 1), 'c'), (Position(1, 0), 'e'), (Position(1, 1), 'f')],
+            sub_grid.into_iter().map(|(position, &c)| (position, c)).collect_vec(),
+        );
+
+        assert!(grid.sub_grid(Position(1, 1), 2, 2).is_none());
+        assert!(grid.sub_grid(Position(-1, 0), 1, 1).is_none());
+        assert_eq!("f", grid.crop(Position(1, 2), 1, 1).unwrap().to_string());
+    }
+
+    #[test]
+    fn windows_cover_every_origin() {
+        let grid = fixture();
+        let windows = grid.windows(2, 2).map(|window| (window.origin(), window.to_grid().to_string())).collect_vec();
+
+        assert_eq!(vec![(Position(0, 0), "ab\nde".to_string()), (Position(0, 1), "bc\nef".to_string())], windows);
+        assert_eq!(3, grid.windows(1, 1).filter(|window| window.origin().0 == 1).count());
+        assert_eq!(0, grid.windows(3, 1).count());
+    }
+
+    #[test]
+    fn render_applies_highlights() {
+        let grid = fixture();
+        let rendered = grid
+            .render()
+            .highlight([Position(0, 0)], Highlight::Char('*'))
+            .highlight([Position(0, 2)], Highlight::ColoredChar('#', Color::Green))
+            .highlight([Position(1, 1), Position(1, 2)], Highlight::Color(Color::Red))
+            .to_string();
+
+        assert_eq!("*b\x1b[32m#\x1b[0m\nd\x1b[31me\x1b[0m\x1b[31mf\x1b[0m", rendered);
+        assert_eq!(grid.to_string(), grid.render().to_string());
+        assert_eq!("#.\n.#", Grid::from_fn(2usize, 2usize, |i, j| i == j).to_string());
+    }
+
+    fn parse_chars(input: &str, options: GridParseOptions<char>) -> std::result::Result<Grid<char>, GridParseError> {
+        Grid::parse_with_options(input, &[], None, options).map(|(grid, _)| grid)
+    }
+
+    #[test]
+    fn parse_rejects_ragged_lines() {
+        assert_eq!(
+            Some(GridParseError::RaggedLine { row: 1, width: 3, expected_width: 2, line: "abc".to_string() }),
+            parse_chars("ab\nabc\n", GridParseOptions::default()).err(),
+        );
+        assert_eq!(
+            Some(GridParseError::RaggedLine { row: 2, width: 1, expected_width: 2, line: "a".to_string() }),
+            parse_chars("ab\nab\na\n", GridParseOptions::default()).err(),
+        );
+    }
+
+    #[test]
+    fn parse_reports_invalid_characters() {
+        let error = Grid::<Direction>::parse_with_options("<>\n^x\n", &[], None, GridParseOptions::default()).err().unwrap();
+
+        assert!(matches!(error, GridParseError::InvalidCharacter { row: 1, col: 1, character: 'x', .. }));
+        assert_eq!(
+            "Unable to parse character 'x' at line 2, column 2: Unable to parse Direction: x\n  |\n2 | ^x\n  |  ^",
+            error.to_string(),
+        );
+    }
+
+    #[test]
+    fn parse_without_lines_fails() {
+        assert_eq!(Some(GridParseError::NoLines), parse_chars("", GridParseOptions::default()).err());
+
+        let error = Grid::<char>::parse("").err().unwrap();
+        assert_eq!(Some(&GridParseError::NoLines), error.downcast_ref::<GridParseError>());
+    }
+
+    #[test]
+    fn parse_pads_short_lines() {
+        let options = GridParseOptions { pad_with: Some('.'), ..GridParseOptions::default() };
+        let grid = parse_chars("ab\na\nabc\n", options).unwrap();
+
+        assert_eq!((3, 3), grid.dimensions::<usize>());
+        assert_eq!("ab.\na..\nabc", grid.to_string());
+    }
+
+    #[test]
+    fn parse_trims_trailing_whitespace() {
+        let options = GridParseOptions { trim_trailing_whitespace: true, ..GridParseOptions::default() };
+
+        assert_eq!("ab\nab", parse_chars("ab  \nab\n", options).unwrap().to_string());
+        assert!(parse_chars("ab  \nab\n", GridParseOptions::default()).is_err());
+        assert_eq!(" a\n b", parse_chars(" a \n b\n", options).unwrap().to_string());
+    }
+}
@@ -0,0 +1,275 @@
+#![allow(dead_code)]
+
+use std::ops::{Add, Mul};
+
+use anyhow::{bail, Result};
+
+const MAX_NODES: usize = 20;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct TourOptions {
+    pub start: Option<usize>,
+    pub end: Option<usize>,
+    pub closed: bool,
+}
+
+impl TourOptions {
+    pub fn open() -> TourOptions {
+        TourOptions::default()
+    }
+
+    pub fn closed() -> TourOptions {
+        TourOptions { closed: true, ..TourOptions::default() }
+    }
+
+    pub fn starting_at(self, start: usize) -> TourOptions {
+        TourOptions { start: Some(start), ..self }
+    }
+
+    pub fn ending_at(self, end: usize) -> TourOptions {
+        TourOptions { end: Some(end), ..self }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Tour<T> {
+    pub value: T,
+    pub order: Vec<usize>,
+}
+
+pub fn held_karp<T>(
+    matrix: &[Vec<Option<T>>],
+    options: TourOptions,
+    identity: T,
+    combine: impl Fn(T, T) -> T,
+    is_better: impl Fn(&T, &T) -> bool,
+) -> Result<Option<Tour<T>>> where
+    T: Copy,
+{
+    let len = matrix.len();
+
+    if len > MAX_NODES {
+        bail!("Held-Karp supports at most {MAX_NODES} nodes, got {len}");
+    }
+
+    if options.closed && options.start.is_some() && options.end.is_some() && options.start != options.end {
+        bail!("A closed tour cannot end at a node other than its start");
+    }
+
+    if len == 0 {
+        return Ok(None);
+    }
+
+    let start = if options.closed { Some(options.start.or(options.end).unwrap_or(0)) } else { options.start };
+    let end = if options.closed { None } else { options.end };
+    let full = (1 << len) - 1;
+    let mut best: Vec<Vec<Option<(T, usize)>>> = vec![vec![None; len]; 1 << len];
+
+    for first in (0..len).filter(|&node| start.is_none_or(|start| start == node)) {
+        best[1 << first][first] = Some((identity, first));
+    }
+
+    for mask in 1..=full {
+        for last in (0..len).filter(|&node| mask >> node & 1 == 1) {
+            let Some((value, _)) = best[mask][last] else { continue; };
+
+            if end == Some(last) && mask != full {
+                continue;
+            }
+
+            for next in (0..len).filter(|&node| mask >> node & 1 == 0) {
+                let Some(cost) = matrix[last][next] else { continue; };
+                let candidate = combine(value, cost);
+                let entry = &mut best[mask | 1 << next][next];
+
+                if entry.is_none_or(|(current, _)| is_better(&candidate, &current)) {
+                    *entry = Some((candidate, last));
+                }
+            }
+        }
+    }
+
+    let mut result: Option<(T, usize)> = None;
+
+    for last in (0..len).filter(|&node| end.is_none_or(|end| end == node)) {
+        let Some((value, _)) = best[full][last] else { continue; };
+
+        let value = match start.filter(|_| options.closed) {
+            Some(start) if len > 1 => {
+                let Some(cost) = matrix[last][start] else { continue; };
+                combine(value, cost)
+            },
+            _ => value,
+        };
+
+        if result.is_none_or(|(current, _)| is_better(&value, &current)) {
+            result = Some((value, last));
+        }
+    }
+
+    let Some((value, mut last)) = result else {
+        return Ok(None);
+    };
+
+    let mut mask = full;
+    let mut order = vec![last];
+
+    while mask != 1 << last {
+        let (_, previous) = best[mask][last].expect("Every node on a reachable tour has a predecessor");
+        mask &= !(1 << last);
+        last = previous;
+        order.push(last);
+    }
+
+    order.reverse();
+
+    Ok(Some(Tour { value, order }))
+}
+
+pub fn shortest_tour<T>(matrix: &[Vec<Option<T>>], options: TourOptions) -> Result<Option<Tour<T>>> where
+    T: Copy + Ord + Default + Add<Output = T>,
+{
+    held_karp(matrix, options, T::default(), |a, b| a + b, |a, b| a < b)
+}
+
+pub fn longest_tour<T>(matrix: &[Vec<Option<T>>], options: TourOptions) -> Result<Option<Tour<T>>> where
+    T: Copy + Ord + Default + Add<Output = T>,
+{
+    held_karp(matrix, options, T::default(), |a, b| a + b, |a, b| a > b)
+}
+
+pub fn max_count_tour<T>(matrix: &[Vec<Option<T>>], options: TourOptions) -> Result<Option<Tour<T>>> where
+    T: Copy + Ord + From<u8> + Mul<Output = T>,
+{
+    held_karp(matrix, options, T::from(1), |a, b| a * b, |a, b| a > b)
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::utils::random::xorshift;
+
+    fn brute_force(matrix: &[Vec<Option<u64>>], options: TourOptions) -> Option<u64> {
+        let len = matrix.len();
+        let start = if options.closed { options.start.or(options.end) } else { options.start };
+        let end = if options.closed { None } else { options.end };
+
+        (0..len)
+            .permutations(len)
+            .filter(|order| start.is_none_or(|start| order[0] == start))
+            .filter(|order| end.is_none_or(|end| order[len - 1] == end))
+            .filter_map(|order| {
+                let closing = (options.closed && len > 1).then(|| (order[len - 1], order[0]));
+
+                order
+                    .iter()
+                    .copied()
+                    .tuple_windows()
+                    .chain(closing)
+                    .map(|(a, b)| matrix[a][b])
+                    .sum::<Option<u64>>()
+            })
+            .min()
+    }
+
+    fn tour_cost(matrix: &[Vec<Option<u64>>], tour: &Tour<u64>, closed: bool) -> u64 {
+        let closing = closed.then(|| (tour.order[tour.order.len() - 1], tour.order[0]));
+
+        tour.order
+            .iter()
+            .copied()
+            .tuple_windows()
+            .chain(closing)
+            .map(|(a, b)| matrix[a][b].unwrap())
+            .sum()
+    }
+
+    const MATRIX: [[u64; 4]; 4] = [
+        [0, 2, 9, 10],
+        [1, 0, 6, 4],
+        [15, 7, 0, 8],
+        [6, 3, 12, 0],
+    ];
+
+    fn matrix() -> Vec<Vec<Option<u64>>> {
+        MATRIX.iter().map(|row| row.iter().map(|&cost| Some(cost)).collect()).collect()
+    }
+
+    #[test]
+    fn closed_tour() {
+        let tour = shortest_tour(&matrix(), TourOptions::closed()).unwrap().unwrap();
+        assert_eq!(21, tour.value);
+        assert_eq!(vec![0, 2, 3, 1], tour.order);
+    }
+
+    #[test]
+    fn closed_tour_with_end_equal_to_start() {
+        let complete = vec![vec![Some(1), Some(2), Some(3)]; 3];
+        let expected = shortest_tour(&complete, TourOptions::closed().starting_at(0)).unwrap().unwrap();
+        let tour = shortest_tour(&complete, TourOptions::closed().starting_at(0).ending_at(0)).unwrap().unwrap();
+
+        assert_eq!(expected, tour);
+        assert_eq!(6, tour.value);
+    }
+
+    #[test]
+    fn open_tour_with_fixed_endpoints() {
+        let tour = shortest_tour(&matrix(), TourOptions::open().starting_at(2).ending_at(0)).unwrap().unwrap();
+        assert_eq!(vec![2, 3, 1, 0], tour.order);
+        assert_eq!(12, tour.value);
+    }
+
+    #[test]
+    fn invalid_options_are_errors() {
+        let too_large = vec![vec![Some(1u64); MAX_NODES + 1]; MAX_NODES + 1];
+        assert!(shortest_tour(&too_large, TourOptions::open()).is_err());
+        assert!(shortest_tour(&matrix(), TourOptions::closed().starting_at(0).ending_at(1)).is_err());
+        assert_eq!(None, shortest_tour::<u64>(&[], TourOptions::open()).unwrap());
+    }
+
+    #[test]
+    fn max_count_tour_multiplies_counts() {
+        let counts = vec![
+            vec![None, Some(3u64), Some(1)],
+            vec![Some(1), None, Some(5)],
+            vec![Some(2), Some(2), None],
+        ];
+
+        let tour = max_count_tour(&counts, TourOptions::open().starting_at(0)).unwrap().unwrap();
+        assert_eq!(15, tour.value);
+        assert_eq!(vec![0, 1, 2], tour.order);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut random = xorshift(31);
+
+        for _ in 0..200 {
+            let len = (random() % 6 + 1) as usize;
+            let matrix = (0..len)
+                .map(|_| (0..len).map(|_| (!random().is_multiple_of(4)).then(|| random() % 10)).collect())
+                .collect_vec();
+            let (start, end) = ((random() % len as u64) as usize, (random() % len as u64) as usize);
+
+            for options in [
+                TourOptions::open(),
+                TourOptions::open().starting_at(start),
+                TourOptions::open().ending_at(end),
+                TourOptions::open().starting_at(start).ending_at(end),
+                TourOptions::closed(),
+                TourOptions::closed().starting_at(start),
+                TourOptions::closed().starting_at(start).ending_at(start),
+            ] {
+                let tour = shortest_tour(&matrix, options).unwrap();
+
+                assert_eq!(brute_force(&matrix, options), tour.as_ref().map(|tour| tour.value));
+
+                if let Some(tour) = tour {
+                    assert_eq!(tour.value, tour_cost(&matrix, &tour, options.closed && len > 1));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,351 @@
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+use hashbrown::HashSet;
+
+use super::graph::{Graph, NodeId};
+
+#[derive(Clone, Copy, Debug)]
+struct FlowEdge {
+    from: usize,
+    to: usize,
+    capacity: u64,
+    residual: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct FlowNetwork {
+    edges: Vec<FlowEdge>,
+    adjacency: Vec<Vec<usize>>,
+    levels: Vec<Option<usize>>,
+    next_edge: Vec<usize>,
+}
+
+impl FlowNetwork {
+    pub fn new(len: usize) -> FlowNetwork {
+        FlowNetwork {
+            edges: vec![],
+            adjacency: vec![vec![]; len],
+            levels: vec![None; len],
+            next_edge: vec![0; len],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adjacency.is_empty()
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, capacity: u64) -> usize {
+        let index = self.edges.len();
+
+        self.edges.push(FlowEdge { from, to, capacity, residual: capacity });
+        self.edges.push(FlowEdge { from: to, to: from, capacity: 0, residual: 0 });
+        self.adjacency[from].push(index);
+        self.adjacency[to].push(index + 1);
+
+        index
+    }
+
+    pub fn flow(&self, edge: usize) -> u64 {
+        self.edges[edge].capacity - self.edges[edge].residual
+    }
+
+    pub fn max_flow(&mut self, source: usize, sink: usize) -> u64 {
+        if source == sink {
+            return 0;
+        }
+
+        let mut total: u64 = 0;
+
+        while self.build_levels(source, sink) {
+            self.next_edge.fill(0);
+
+            while let Some(pushed) = self.augment(source, sink) {
+                total = total.saturating_add(pushed);
+            }
+        }
+
+        total
+    }
+
+    pub fn source_side(&self, source: usize) -> Vec<bool> {
+        let mut reachable = vec![false; self.len()];
+        let mut queue = VecDeque::from([source]);
+        reachable[source] = true;
+
+        while let Some(current) = queue.pop_front() {
+            for &edge in &self.adjacency[current] {
+                let FlowEdge { to, .. } = self.edges[edge];
+
+                if self.edges[edge].residual > 0 && !reachable[to] {
+                    reachable[to] = true;
+                    queue.push_back(to);
+                }
+            }
+        }
+
+        reachable
+    }
+
+    pub fn cut_edges(&self, source: usize) -> Vec<usize> {
+        let source_side = self.source_side(source);
+
+        (0..self.edges.len())
+            .step_by(2)
+            .filter(|&edge| {
+                let FlowEdge { from, to, capacity, .. } = self.edges[edge];
+                capacity > 0 && source_side[from] && !source_side[to]
+            })
+            .collect()
+    }
+
+    pub fn endpoints(&self, edge: usize) -> (usize, usize) {
+        (self.edges[edge].from, self.edges[edge].to)
+    }
+
+    fn build_levels(&mut self, source: usize, sink: usize) -> bool {
+        self.levels.fill(None);
+        self.levels[source] = Some(0);
+
+        let mut queue = VecDeque::from([source]);
+
+        while let Some(current) = queue.pop_front() {
+            let level = self.levels[current].unwrap();
+
+            for &edge in &self.adjacency[current] {
+                let FlowEdge { to, .. } = self.edges[edge];
+
+                if self.edges[edge].residual > 0 && self.levels[to].is_none() {
+                    self.levels[to] = Some(level + 1);
+                    queue.push_back(to);
+                }
+            }
+        }
+
+        self.levels[sink].is_some()
+    }
+
+    fn augment(&mut self, source: usize, sink: usize) -> Option<u64> {
+        let mut path: Vec<usize> = vec![];
+        let mut current = source;
+
+        while current != sink {
+            if let Some(&edge) = self.adjacency[current].get(self.next_edge[current]) {
+                let FlowEdge { to, residual, .. } = self.edges[edge];
+
+                if residual > 0 && self.levels[to] == self.levels[current].map(|level| level + 1) {
+                    path.push(edge);
+                    current = to;
+                } else {
+                    self.next_edge[current] += 1;
+                }
+            } else {
+                current = self.edges[path.pop()?].from;
+                self.next_edge[current] += 1;
+            }
+        }
+
+        let pushed = path.iter().map(|&edge| self.edges[edge].residual).min()?;
+
+        for edge in path {
+            self.edges[edge].residual -= pushed;
+            self.edges[edge ^ 1].residual += pushed;
+        }
+
+        Some(pushed)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MinCut {
+    pub value: u64,
+    pub source_side: HashSet<NodeId>,
+    pub edges: Vec<(NodeId, NodeId)>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MinVertexCut {
+    pub value: u64,
+    pub nodes: Vec<NodeId>,
+}
+
+impl<N, W> Graph<N, W> where N: Clone + Eq + Hash {
+    pub fn min_cut(&self, source: NodeId, sink: NodeId, capacity: impl Fn(&W) -> u64) -> MinCut {
+        let mut network = FlowNetwork::new(self.len());
+
+        for from in self.node_ids() {
+            for (to, weight) in self.out_edges(from) {
+                network.add_edge(from as usize, *to as usize, capacity(weight));
+            }
+        }
+
+        let value = network.max_flow(source as usize, sink as usize);
+        let source_side = network
+            .source_side(source as usize)
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, is_source_side)| is_source_side)
+            .map(|(id, _)| id as NodeId)
+            .collect();
+
+        let edges = network
+            .cut_edges(source as usize)
+            .into_iter()
+            .map(|edge| network.endpoints(edge))
+            .map(|(from, to)| (from as NodeId, to as NodeId))
+            .collect();
+
+        MinCut { value, source_side, edges }
+    }
+
+    pub fn max_flow(&self, source: NodeId, sink: NodeId, capacity: impl Fn(&W) -> u64) -> u64 {
+        self.min_cut(source, sink, capacity).value
+    }
+
+    pub fn edge_disjoint_paths(&self, source: NodeId, sink: NodeId) -> u64 {
+        self.max_flow(source, sink, |_| 1)
+    }
+
+    pub fn min_vertex_cut(&self, source: NodeId, sink: NodeId) -> Option<MinVertexCut> {
+        if source == sink || self.successors(source).any(|successor| successor == sink) {
+            return None;
+        }
+
+        let node_in = |id: NodeId| 2 * id as usize;
+        let node_out = |id: NodeId| 2 * id as usize + 1;
+        let mut network = FlowNetwork::new(2 * self.len());
+
+        for id in self.node_ids() {
+            let capacity = if id == source || id == sink { u64::MAX } else { 1 };
+            network.add_edge(node_in(id), node_out(id), capacity);
+
+            for successor in self.successors(id) {
+                network.add_edge(node_out(id), node_in(successor), u64::MAX);
+            }
+        }
+
+        let value = network.max_flow(node_out(source), node_in(sink));
+        let nodes = network
+            .cut_edges(node_out(source))
+            .into_iter()
+            .map(|edge| network.endpoints(edge))
+            .filter(|&(from, to)| from % 2 == 0 && to == from + 1)
+            .map(|(from, _)| (from / 2) as NodeId)
+            .collect();
+
+        Some(MinVertexCut { value, nodes })
+    }
+
+    pub fn vertex_disjoint_paths(&self, source: NodeId, sink: NodeId) -> Option<u64> {
+        self.min_vertex_cut(source, sink).map(|cut| cut.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::random::xorshift;
+
+    fn classic_network() -> Graph<&'static str, u64> {
+        Graph::from_edges([
+            ("s", "v1", 16),
+            ("s", "v2", 13),
+            ("v1", "v3", 12),
+            ("v2", "v1", 4),
+            ("v2", "v4", 14),
+            ("v3", "v2", 9),
+            ("v3", "t", 20),
+            ("v4", "v3", 7),
+            ("v4", "t", 4),
+        ])
+    }
+
+    #[test]
+    fn min_cut_of_classic_network() {
+        let graph = classic_network();
+        let id = |node| graph.id(&node).unwrap();
+        let cut = graph.min_cut(id("s"), id("t"), |&capacity| capacity);
+
+        assert_eq!(23, cut.value);
+        assert_eq!(HashSet::from_iter(["s", "v1", "v2", "v4"].map(id)), cut.source_side);
+
+        let mut edges = cut.edges.iter().map(|&(from, to)| (*graph.node(from), *graph.node(to))).collect::<Vec<_>>();
+        edges.sort();
+        assert_eq!(vec![("v1", "v3"), ("v4", "t"), ("v4", "v3")], edges);
+    }
+
+    #[test]
+    fn max_flow_matches_brute_force_min_cut() {
+        let mut random = xorshift(17);
+
+        for _ in 0..300 {
+            let len = (random() % 6 + 2) as usize;
+            let edges = (0..random() % 20)
+                .map(|_| ((random() % len as u64) as usize, (random() % len as u64) as usize, random() % 10))
+                .collect::<Vec<_>>();
+            let mut network = FlowNetwork::new(len);
+
+            for &(from, to, capacity) in &edges {
+                network.add_edge(from, to, capacity);
+            }
+
+            let expected = (0..1usize << len)
+                .filter(|&side| side & 1 == 1 && side >> (len - 1) & 1 == 0)
+                .map(|side| {
+                    edges
+                        .iter()
+                        .filter(|&&(from, to, _)| side >> from & 1 == 1 && side >> to & 1 == 0)
+                        .map(|&(_, _, capacity)| capacity)
+                        .sum::<u64>()
+                })
+                .min()
+                .unwrap();
+
+            assert_eq!(expected, network.max_flow(0, len - 1));
+
+            let cut_value = network
+                .cut_edges(0)
+                .into_iter()
+                .map(|edge| network.edges[edge].capacity)
+                .sum::<u64>();
+            assert_eq!(expected, cut_value);
+        }
+    }
+
+    #[test]
+    fn long_chain_does_not_overflow_the_stack() {
+        let len = 200_000;
+        let mut network = FlowNetwork::new(len);
+
+        for node in 1..len {
+            network.add_edge(node - 1, node, 3);
+        }
+
+        assert_eq!(3, network.max_flow(0, len - 1));
+    }
+
+    #[test]
+    fn disjoint_paths_and_vertex_cut() {
+        let graph: Graph<char> = Graph::from_edges([
+            ('s', 'a', ()),
+            ('s', 'b', ()),
+            ('a', 'h', ()),
+            ('b', 'h', ()),
+            ('a', 'h', ()),
+            ('h', 't', ()),
+            ('h', 't', ()),
+        ]);
+        let id = |node| graph.id(&node).unwrap();
+
+        assert_eq!(2, graph.edge_disjoint_paths(id('s'), id('t')));
+        assert_eq!(Some(1), graph.vertex_disjoint_paths(id('s'), id('t')));
+        assert_eq!(vec![id('h')], graph.min_vertex_cut(id('s'), id('t')).unwrap().nodes);
+        assert!(graph.min_vertex_cut(id('h'), id('t')).is_none());
+    }
+}
@@ -3,15 +3,134 @@
 use std::cmp::{Ordering, Reverse};
 use std::collections::{BinaryHeap, VecDeque};
 use std::hash::Hash;
+use std::iter;
+use std::ops::Add;
+use std::sync::atomic::{self, AtomicBool};
+use std::time::{Duration, Instant};
 
+use hashbrown::hash_map::Entry;
 use hashbrown::{HashMap, HashSet};
 use itertools::Itertools;
 
-type Distances<SearchNode> = HashMap<SearchNode, (usize, Vec<SearchNode>)>;
+#[allow(unused_imports)]
+pub use ordered_float::OrderedFloat;
+
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C> Cost for C where C: Copy + Ord + Add<Output = C> + Default {}
+
+type Distances<SearchNode, C> = HashMap<SearchNode, (C, Vec<SearchNode>)>;
 pub type PathMap<SearchNode> = HashMap<SearchNode, Vec<SearchNode>>;
 
-fn resolve_path_map<SearchNode>(
-    distances: &Distances<SearchNode>,
+fn relax<SearchNode, C>(
+    distances: &mut Distances<SearchNode, C>,
+    current: &SearchNode,
+    neighbor: SearchNode,
+    distance: C,
+    cost: C,
+) -> bool where
+    SearchNode: Clone + Hash + Eq,
+    C: Cost,
+{
+    let candidate = distance + cost;
+
+    match distances.entry(neighbor) {
+        Entry::Vacant(entry) => {
+            entry.insert((candidate, vec![current.clone()]));
+            true
+        }
+        Entry::Occupied(mut entry) => {
+            let (neighbor_distance, neighbor_sources) = entry.get_mut();
+
+            match candidate.cmp(neighbor_distance) {
+                Ordering::Less => {
+                    *neighbor_distance = candidate;
+                    *neighbor_sources = vec![current.clone()];
+                    true
+                }
+                Ordering::Equal => {
+                    neighbor_sources.push(current.clone());
+                    false
+                }
+                _ => false,
+            }
+        }
+    }
+}
+
+fn break_zero_cost_cycles<SearchNode, C>(
+    distances: &mut Distances<SearchNode, C>,
+    roots: impl IntoIterator<Item = SearchNode>,
+) where
+    SearchNode: Clone + Hash + Eq,
+    C: Cost,
+{
+    let has_zero_cost_ties = distances.values().any(|(distance, predecessors)| {
+        predecessors
+            .iter()
+            .any(|predecessor| distances.get(predecessor).is_some_and(|(previous, _)| previous == distance))
+    });
+
+    if !has_zero_cost_ties {
+        return;
+    }
+
+    let mut successors: PathMap<SearchNode> = HashMap::new();
+
+    for (node, (_, predecessors)) in distances.iter() {
+        for predecessor in predecessors.iter().unique() {
+            successors.entry(predecessor.clone()).or_default().push(node.clone());
+        }
+    }
+
+    for (from, to) in back_edges(&successors, roots) {
+        if let Some((_, predecessors)) = distances.get_mut(&to) {
+            predecessors.retain(|predecessor| *predecessor != from);
+        }
+    }
+}
+
+fn back_edges<SearchNode>(
+    path_map: &PathMap<SearchNode>,
+    roots: impl IntoIterator<Item = SearchNode>,
+) -> Vec<(SearchNode, SearchNode)> where
+    SearchNode: Clone + Hash + Eq,
+{
+    let mut on_stack: HashMap<SearchNode, bool> = HashMap::new();
+    let mut back_edges = vec![];
+
+    for root in roots {
+        if on_stack.contains_key(&root) {
+            continue;
+        }
+
+        on_stack.insert(root.clone(), true);
+        let mut stack = vec![(root, 0)];
+
+        while let Some((current, index)) = stack.last_mut() {
+            if let Some(next) = successors(path_map, current).get(*index) {
+                *index += 1;
+
+                match on_stack.get(next) {
+                    None => {
+                        on_stack.insert(next.clone(), true);
+                        stack.push((next.clone(), 0));
+                    },
+                    Some(true) => back_edges.push((current.clone(), next.clone())),
+                    Some(false) => {},
+                }
+            } else {
+                on_stack.insert(current.clone(), false);
+                stack.pop();
+            }
+        }
+    }
+
+    back_edges
+}
+
+fn resolve_path_map<SearchNode, C>(
+    distances: &Distances<SearchNode, C>,
     targets: &[SearchNode]
 ) -> PathMap<SearchNode> where
     SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
@@ -40,25 +159,279 @@ fn resolve_path_map<SearchNode>(
     path_map
 }
 
-pub fn shortest_paths_to_target<SearchNode, IsTargetFn> (
+fn successors<'a, SearchNode>(path_map: &'a PathMap<SearchNode>, node: &SearchNode) -> &'a [SearchNode] where
+    SearchNode: Hash + Eq,
+{
+    path_map.get(node).map(Vec::as_slice).unwrap_or_default()
+}
+
+pub fn shortest_path<SearchNode>(source: &SearchNode, path_map: &PathMap<SearchNode>) -> Option<Vec<SearchNode>> where
+    SearchNode: Clone + Ord + Hash,
+{
+    if !path_map.contains_key(source) {
+        return None;
+    }
+
+    let mut path = vec![source.clone()];
+
+    while let Some(next) = successors(path_map, path.last()?).iter().min() {
+        path.push(next.clone());
+    }
+
+    Some(path)
+}
+
+pub struct ShortestPaths<'a, SearchNode> {
+    path_map: &'a PathMap<SearchNode>,
+    stack: Vec<(SearchNode, usize)>,
+}
+
+impl<SearchNode> Iterator for ShortestPaths<'_, SearchNode> where
+    SearchNode: Clone + Hash + Eq,
+{
+    type Item = Vec<SearchNode>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((current, index)) = self.stack.last_mut() {
+            let next_nodes = successors(self.path_map, current);
+
+            if next_nodes.is_empty() {
+                let path = self.stack.iter().map(|(node, _)| node.clone()).collect_vec();
+                self.stack.pop();
+                return Some(path);
+            }
+
+            if let Some(next) = next_nodes.get(*index) {
+                *index += 1;
+                self.stack.push((next.clone(), 0));
+            } else {
+                self.stack.pop();
+            }
+        }
+
+        None
+    }
+}
+
+pub fn all_shortest_paths<'a, SearchNode>(source: &SearchNode, path_map: &'a PathMap<SearchNode>) -> ShortestPaths<'a, SearchNode> where
+    SearchNode: Clone + Hash + Eq,
+{
+    let stack = if path_map.contains_key(source) {
+        vec![(source.clone(), 0)]
+    } else {
+        vec![]
+    };
+
+    ShortestPaths { path_map, stack }
+}
+
+fn topological_order<SearchNode>(source: &SearchNode, path_map: &PathMap<SearchNode>) -> Vec<SearchNode> where
+    SearchNode: Clone + Hash + Eq,
+{
+    let mut order = vec![];
+    let mut seen: HashSet<SearchNode> = HashSet::from_iter([source.clone()]);
+    let mut stack = vec![(source.clone(), 0)];
+
+    while let Some((current, index)) = stack.last_mut() {
+        if let Some(next) = successors(path_map, current).get(*index) {
+            *index += 1;
+
+            if seen.insert(next.clone()) {
+                stack.push((next.clone(), 0));
+            }
+        } else {
+            order.push(current.clone());
+            stack.pop();
+        }
+    }
+
+    order.reverse();
+    order
+}
+
+pub fn count_shortest_paths<SearchNode>(source: &SearchNode, path_map: &PathMap<SearchNode>) -> u128 where
+    SearchNode: Clone + Hash + Eq,
+{
+    if !path_map.contains_key(source) {
+        return 0;
+    }
+
+    let mut counts: HashMap<SearchNode, u128> = HashMap::new();
+
+    for node in topological_order(source, path_map).into_iter().rev() {
+        let next_nodes = successors(path_map, &node);
+        let count = if next_nodes.is_empty() {
+            1
+        } else {
+            next_nodes.iter().map(|next| counts[next]).sum()
+        };
+
+        counts.insert(node, count);
+    }
+
+    counts[source]
+}
+
+pub fn nodes_on_shortest_paths<SearchNode>(source: &SearchNode, path_map: &PathMap<SearchNode>) -> HashSet<SearchNode> where
+    SearchNode: Clone + Hash + Eq,
+{
+    if !path_map.contains_key(source) {
+        return HashSet::new();
+    }
+
+    topological_order(source, path_map).into_iter().collect()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SearchStats {
+    pub nodes_expanded: usize,
+    pub peak_queue_len: usize,
+    pub elapsed: Duration,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SearchStatus {
+    Finished,
+    MaxCostReached,
+    MaxExpandedNodesReached,
+    MaxFrontierSizeReached,
+    Cancelled,
+}
+
+#[derive(Clone, Debug)]
+pub struct SearchResult<T> {
+    pub value: T,
+    pub status: SearchStatus,
+    pub stats: SearchStats,
+}
+
+impl<T> SearchResult<T> {
+    pub fn is_finished(&self) -> bool {
+        self.status == SearchStatus::Finished
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SearchOptions<'a, C> {
+    pub max_cost: Option<C>,
+    pub max_expanded_nodes: Option<usize>,
+    pub max_frontier_size: Option<usize>,
+    pub cancelled: Option<&'a AtomicBool>,
+}
+
+impl<'a, C> SearchOptions<'a, C> where C: Cost {
+    pub fn new() -> SearchOptions<'a, C> {
+        SearchOptions::default()
+    }
+
+    pub fn with_max_cost(self, max_cost: C) -> SearchOptions<'a, C> {
+        SearchOptions { max_cost: Some(max_cost), ..self }
+    }
+
+    pub fn with_max_expanded_nodes(self, max_expanded_nodes: usize) -> SearchOptions<'a, C> {
+        SearchOptions { max_expanded_nodes: Some(max_expanded_nodes), ..self }
+    }
+
+    pub fn with_max_frontier_size(self, max_frontier_size: usize) -> SearchOptions<'a, C> {
+        SearchOptions { max_frontier_size: Some(max_frontier_size), ..self }
+    }
+
+    pub fn with_cancellation(self, cancelled: &'a AtomicBool) -> SearchOptions<'a, C> {
+        SearchOptions { cancelled: Some(cancelled), ..self }
+    }
+
+    fn limit_reached(&self, priority: C, stats: &SearchStats) -> Option<SearchStatus> {
+        if self.cancelled.is_some_and(|cancelled| cancelled.load(atomic::Ordering::Relaxed)) {
+            Some(SearchStatus::Cancelled)
+        } else if self.max_cost.is_some_and(|max_cost| priority > max_cost) {
+            Some(SearchStatus::MaxCostReached)
+        } else if self.max_expanded_nodes.is_some_and(|max_expanded_nodes| stats.nodes_expanded >= max_expanded_nodes) {
+            Some(SearchStatus::MaxExpandedNodesReached)
+        } else if self.max_frontier_size.is_some_and(|max_frontier_size| stats.peak_queue_len > max_frontier_size) {
+            Some(SearchStatus::MaxFrontierSizeReached)
+        } else {
+            None
+        }
+    }
+}
+
+pub fn shortest_paths_to_target<SearchNode, C, Neighbors, IsTargetFn> (
     source: SearchNode,
-    neighbors: impl Fn(&SearchNode) -> Vec<(SearchNode, usize)>,
+    neighbors: impl Fn(&SearchNode) -> Neighbors,
     is_target: IsTargetFn,
-) -> Option<(usize, PathMap<SearchNode>)> where
+) -> Option<(C, PathMap<SearchNode>)> where
     SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    C: Cost,
+    Neighbors: IntoIterator<Item = (SearchNode, C)>,
     IsTargetFn: Fn(&SearchNode) -> bool + Copy,
 {
-    let (distances, reached_targets) = distances_impl(
+    let (result, _) = shortest_paths_to_target_with_heuristic(
         source,
         neighbors,
         is_target,
-        true
+        |_| C::default(),
     );
 
-    if reached_targets.is_empty() {
-        return None;
+    result
+}
+
+pub fn shortest_paths_to_target_with_heuristic<SearchNode, C, Neighbors, IsTargetFn> (
+    source: SearchNode,
+    neighbors: impl Fn(&SearchNode) -> Neighbors,
+    is_target: IsTargetFn,
+    heuristic: impl Fn(&SearchNode) -> C,
+) -> (Option<(C, PathMap<SearchNode>)>, SearchStats) where
+    SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    C: Cost,
+    Neighbors: IntoIterator<Item = (SearchNode, C)>,
+    IsTargetFn: Fn(&SearchNode) -> bool + Copy,
+{
+    let result = shortest_paths_to_target_with_options(
+        source,
+        neighbors,
+        is_target,
+        heuristic,
+        &SearchOptions::default(),
+    );
+
+    (result.value, result.stats)
+}
+
+pub fn shortest_paths_to_target_with_options<SearchNode, C, Neighbors, IsTargetFn> (
+    source: SearchNode,
+    neighbors: impl Fn(&SearchNode) -> Neighbors,
+    is_target: IsTargetFn,
+    heuristic: impl Fn(&SearchNode) -> C,
+    options: &SearchOptions<C>,
+) -> SearchResult<Option<(C, PathMap<SearchNode>)>> where
+    SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    C: Cost,
+    Neighbors: IntoIterator<Item = (SearchNode, C)>,
+    IsTargetFn: Fn(&SearchNode) -> bool + Copy,
+{
+    let (distances, reached_targets, status, stats) = distances_impl(
+        source,
+        neighbors,
+        is_target,
+        heuristic,
+        true,
+        options,
+    );
+
+    SearchResult {
+        value: resolve_targets(&distances, &reached_targets),
+        status,
+        stats,
     }
+}
 
+fn resolve_targets<SearchNode, C>(
+    distances: &Distances<SearchNode, C>,
+    reached_targets: &[SearchNode],
+) -> Option<(C, PathMap<SearchNode>)> where
+    SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    C: Cost,
+{
     let potential_targets = reached_targets
         .iter()
         .filter_map(|target| Some((target, distances.get(target)?)))
@@ -75,75 +448,864 @@ pub fn shortest_paths_to_target<SearchNode, IsTargetFn> (
         .map(|&(node, _)| node.clone())
         .collect_vec();
 
-    Some((min_distance, resolve_path_map(&distances, &targets)))
+    Some((min_distance, resolve_path_map(distances, &targets)))
 }
 
-pub fn distance_to_target<SearchNode, IsTargetFn> (
+pub fn bfs_distance<SearchNode, Neighbors, IsTargetFn> (
     source: SearchNode,
-    neighbors: impl Fn(&SearchNode) -> Vec<(SearchNode, usize)>,
+    neighbors: impl Fn(&SearchNode) -> Neighbors,
     is_target: IsTargetFn,
-) -> Option<usize> where
+) -> Option<(usize, PathMap<SearchNode>)> where
+    SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    Neighbors: IntoIterator<Item = (SearchNode, usize)>,
+    IsTargetFn: Fn(&SearchNode) -> bool + Copy,
+{
+    bfs_distance_with_options(source, neighbors, is_target, &SearchOptions::default()).value
+}
+
+pub fn bfs_distance_with_options<SearchNode, Neighbors, IsTargetFn> (
+    source: SearchNode,
+    neighbors: impl Fn(&SearchNode) -> Neighbors,
+    is_target: IsTargetFn,
+    options: &SearchOptions<usize>,
+) -> SearchResult<Option<(usize, PathMap<SearchNode>)>> where
+    SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    Neighbors: IntoIterator<Item = (SearchNode, usize)>,
+    IsTargetFn: Fn(&SearchNode) -> bool + Copy,
+{
+    let neighbors = |node: &SearchNode| {
+        neighbors(node)
+            .into_iter()
+            .inspect(|(_, cost)| assert_eq!(*cost, 1, "bfs_distance requires unit edge costs"))
+    };
+
+    let (distances, reached_targets, status, stats) = deque_distances_impl(source, neighbors, is_target, options);
+
+    SearchResult {
+        value: resolve_targets(&distances, &reached_targets),
+        status,
+        stats,
+    }
+}
+
+pub fn zero_one_bfs<SearchNode, Neighbors, IsTargetFn> (
+    source: SearchNode,
+    neighbors: impl Fn(&SearchNode) -> Neighbors,
+    is_target: IsTargetFn,
+) -> Option<(usize, PathMap<SearchNode>)> where
+    SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    Neighbors: IntoIterator<Item = (SearchNode, usize)>,
+    IsTargetFn: Fn(&SearchNode) -> bool + Copy,
+{
+    zero_one_bfs_with_options(source, neighbors, is_target, &SearchOptions::default()).value
+}
+
+pub fn zero_one_bfs_with_options<SearchNode, Neighbors, IsTargetFn> (
+    source: SearchNode,
+    neighbors: impl Fn(&SearchNode) -> Neighbors,
+    is_target: IsTargetFn,
+    options: &SearchOptions<usize>,
+) -> SearchResult<Option<(usize, PathMap<SearchNode>)>> where
+    SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    Neighbors: IntoIterator<Item = (SearchNode, usize)>,
+    IsTargetFn: Fn(&SearchNode) -> bool + Copy,
+{
+    let (distances, reached_targets, status, stats) = deque_distances_impl(source, neighbors, is_target, options);
+
+    SearchResult {
+        value: resolve_targets(&distances, &reached_targets),
+        status,
+        stats,
+    }
+}
+
+fn deque_distances_impl<SearchNode, Neighbors, IsTargetFn> (
+    source: SearchNode,
+    neighbors: impl Fn(&SearchNode) -> Neighbors,
+    is_target: IsTargetFn,
+    options: &SearchOptions<usize>,
+) -> (Distances<SearchNode, usize>, Vec<SearchNode>, SearchStatus, SearchStats) where
+    SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    Neighbors: IntoIterator<Item = (SearchNode, usize)>,
+    IsTargetFn: Fn(&SearchNode) -> bool + Copy,
+{
+    let start = Instant::now();
+    let mut distances = HashMap::from([(source.clone(), (0, vec![]))]);
+    let mut queue = VecDeque::from([(0, source.clone())]);
+    let mut targets_reached = vec![];
+    let mut target_distance = None;
+    let mut status = SearchStatus::Finished;
+    let mut stats = SearchStats { peak_queue_len: 1, ..SearchStats::default() };
+
+    while let Some((distance, current)) = queue.pop_front() {
+        if distances.get(&current).is_some_and(|(best, _)| distance > *best) {
+            continue;
+        }
+
+        if target_distance.is_some_and(|target_distance| distance > target_distance) {
+            break;
+        }
+
+        if let Some(limit) = options.limit_reached(distance, &stats) {
+            status = limit;
+            break;
+        }
+
+        if is_target(&current) {
+            targets_reached.push(current.clone());
+            target_distance = Some(distance);
+            continue;
+        }
+
+        stats.nodes_expanded += 1;
+
+        for (neighbor, cost) in neighbors(&current) {
+            assert!(cost <= 1, "zero_one_bfs requires edge costs of 0 or 1");
+
+            if relax(&mut distances, &current, neighbor.clone(), distance, cost) {
+                if cost == 0 {
+                    queue.push_front((distance, neighbor));
+                } else {
+                    queue.push_back((distance + cost, neighbor));
+                }
+            }
+        }
+
+        stats.peak_queue_len = stats.peak_queue_len.max(queue.len());
+    }
+
+    break_zero_cost_cycles(&mut distances, [source]);
+    stats.elapsed = start.elapsed();
+
+    (distances, targets_reached, status, stats)
+}
+
+pub fn distance_to_target<SearchNode, C, Neighbors, IsTargetFn> (
+    source: SearchNode,
+    neighbors: impl Fn(&SearchNode) -> Neighbors,
+    is_target: IsTargetFn,
+) -> Option<C> where
     SearchNode: Copy + Clone + PartialEq + PartialOrd + Ord + Hash,
+    C: Cost,
+    Neighbors: IntoIterator<Item = (SearchNode, C)>,
     IsTargetFn: Fn(&SearchNode) -> bool + Copy,
 {
-    let (distances, targets_reached) = distances_impl(
+    distance_to_target_with_options(source, neighbors, is_target, &SearchOptions::default()).value
+}
+
+pub fn distance_to_target_with_options<SearchNode, C, Neighbors, IsTargetFn> (
+    source: SearchNode,
+    neighbors: impl Fn(&SearchNode) -> Neighbors,
+    is_target: IsTargetFn,
+    options: &SearchOptions<C>,
+) -> SearchResult<Option<C>> where
+    SearchNode: Copy + Clone + PartialEq + PartialOrd + Ord + Hash,
+    C: Cost,
+    Neighbors: IntoIterator<Item = (SearchNode, C)>,
+    IsTargetFn: Fn(&SearchNode) -> bool + Copy,
+{
+    let (distances, targets_reached, status, stats) = distances_impl(
         source,
         neighbors,
         is_target,
+        |_| C::default(),
         false,
+        options,
     );
 
-    if targets_reached.is_empty() {
-        return None;
-    }
-
-    targets_reached
+    let value = targets_reached
         .iter()
         .filter_map(|target| distances.get(target))
         .map(|(a, _)| *a)
-        .min()
+        .min();
+
+    SearchResult { value, status, stats }
 }
 
-fn distances_impl<SearchNode, IsTargetFn> (
+fn distances_impl<SearchNode, C, Neighbors, IsTargetFn> (
     source: SearchNode,
-    neighbors: impl Fn(&SearchNode) -> Vec<(SearchNode, usize)>,
+    neighbors: impl Fn(&SearchNode) -> Neighbors,
     is_target: IsTargetFn,
+    heuristic: impl Fn(&SearchNode) -> C,
     break_at_target: bool,
-) -> (Distances<SearchNode>, Vec<SearchNode>) where
+    options: &SearchOptions<C>,
+) -> (Distances<SearchNode, C>, Vec<SearchNode>, SearchStatus, SearchStats) where
     SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    C: Cost,
+    Neighbors: IntoIterator<Item = (SearchNode, C)>,
     IsTargetFn: Fn(&SearchNode) -> bool + Copy,
 {
-    let mut distances = HashMap::from([(source.clone(), (0, vec![]))]);
-    let mut queue = BinaryHeap::from([(Reverse(0), source.clone())]);
+    let start = Instant::now();
+    let mut distances = HashMap::from([(source.clone(), (C::default(), vec![]))]);
+    let mut queue = BinaryHeap::from([(Reverse(heuristic(&source)), Reverse(C::default()), source.clone())]);
     let mut targets_reached = vec![];
+    let mut target_priority = None;
+    let mut status = SearchStatus::Finished;
+    let mut stats = SearchStats { peak_queue_len: 1, ..SearchStats::default() };
+
+    while let Some((Reverse(priority), Reverse(distance), current)) = queue.pop() {
+        if distances.get(&current).is_some_and(|(best, _)| distance > *best) {
+            continue;
+        }
+
+        if target_priority.is_some_and(|target_priority| priority > target_priority) {
+            break;
+        }
+
+        if let Some(limit) = options.limit_reached(priority, &stats) {
+            status = limit;
+            break;
+        }
 
-    while let Some((Reverse(distance), current)) = queue.pop() {
         if is_target(&current) {
             targets_reached.push(current.clone());
 
             if break_at_target {
+                target_priority = Some(priority);
+                continue;
+            }
+        }
+
+        stats.nodes_expanded += 1;
+
+        for (neighbor, cost) in neighbors(&current) {
+            if relax(&mut distances, &current, neighbor.clone(), distance, cost) {
+                queue.push((Reverse(distance + cost + heuristic(&neighbor)), Reverse(distance + cost), neighbor));
+            }
+        }
+
+        stats.peak_queue_len = stats.peak_queue_len.max(queue.len());
+    }
+
+    break_zero_cost_cycles(&mut distances, [source]);
+    stats.elapsed = start.elapsed();
+
+    (distances, targets_reached, status, stats)
+}
+
+pub struct DistanceMap<SearchNode, C> where SearchNode: Hash + Eq {
+    distances: Distances<SearchNode, C>,
+    nearest_sources: HashMap<SearchNode, SearchNode>,
+    settled_targets: Vec<SearchNode>,
+}
+
+impl<SearchNode, C> DistanceMap<SearchNode, C> where
+    SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    C: Cost,
+{
+    pub fn distance(&self, node: &SearchNode) -> Option<C> {
+        self.distances.get(node).map(|(distance, _)| *distance)
+    }
+
+    pub fn predecessors(&self, node: &SearchNode) -> &[SearchNode] {
+        self.distances.get(node).map(|(_, predecessors)| predecessors.as_slice()).unwrap_or_default()
+    }
+
+    pub fn nearest_source(&self, node: &SearchNode) -> Option<&SearchNode> {
+        self.nearest_sources.get(node)
+    }
+
+    pub fn settled_targets(&self) -> &[SearchNode] {
+        &self.settled_targets
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = (&SearchNode, C)> {
+        self.distances.iter().map(|(node, (distance, _))| (node, *distance))
+    }
+
+    pub fn path_map(&self, targets: &[SearchNode]) -> PathMap<SearchNode> {
+        resolve_path_map(&self.distances, targets)
+    }
+}
+
+pub fn multi_source_distances<SearchNode, C, Neighbors, IsTargetFn> (
+    sources: impl IntoIterator<Item = (SearchNode, Option<C>)>,
+    neighbors: impl Fn(&SearchNode) -> Neighbors,
+    is_target: IsTargetFn,
+    max_targets: Option<usize>,
+) -> DistanceMap<SearchNode, C> where
+    SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    C: Cost,
+    Neighbors: IntoIterator<Item = (SearchNode, C)>,
+    IsTargetFn: Fn(&SearchNode) -> bool,
+{
+    multi_source_distances_with_options(sources, neighbors, is_target, max_targets, &SearchOptions::default()).value
+}
+
+pub fn multi_source_distances_with_options<SearchNode, C, Neighbors, IsTargetFn> (
+    sources: impl IntoIterator<Item = (SearchNode, Option<C>)>,
+    neighbors: impl Fn(&SearchNode) -> Neighbors,
+    is_target: IsTargetFn,
+    max_targets: Option<usize>,
+    options: &SearchOptions<C>,
+) -> SearchResult<DistanceMap<SearchNode, C>> where
+    SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    C: Cost,
+    Neighbors: IntoIterator<Item = (SearchNode, C)>,
+    IsTargetFn: Fn(&SearchNode) -> bool,
+{
+    let start = Instant::now();
+    let mut distances: Distances<SearchNode, C> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+
+    for (source, initial_cost) in sources {
+        let initial_cost = initial_cost.unwrap_or_default();
+
+        if distances.get(&source).is_none_or(|(distance, _)| initial_cost < *distance) {
+            distances.insert(source.clone(), (initial_cost, vec![]));
+            queue.push((Reverse(initial_cost), source));
+        }
+    }
+
+    let mut settled = vec![];
+    let mut settled_targets = vec![];
+    let mut status = SearchStatus::Finished;
+    let mut stats = SearchStats { peak_queue_len: queue.len(), ..SearchStats::default() };
+
+    while let Some((Reverse(distance), current)) = queue.peek().cloned() {
+        if distances.get(&current).is_some_and(|(best, _)| distance > *best) {
+            queue.pop();
+            continue;
+        }
+
+        if let Some(limit) = options.limit_reached(distance, &stats) {
+            status = limit;
+            break;
+        }
+
+        queue.pop();
+        settled.push(current.clone());
+
+        if is_target(&current) {
+            settled_targets.push(current.clone());
+
+            if max_targets.is_some_and(|max_targets| settled_targets.len() >= max_targets) {
                 break;
             }
         }
 
+        stats.nodes_expanded += 1;
+
         for (neighbor, cost) in neighbors(&current) {
-            let (neighbor_distance, neighbor_sources) = distances
-                .entry(neighbor.clone())
-                .or_insert((usize::MAX, vec![]));
+            if relax(&mut distances, &current, neighbor.clone(), distance, cost) {
+                queue.push((Reverse(distance + cost), neighbor));
+            }
+        }
 
-            match (distance + cost).cmp(neighbor_distance) {
-                Ordering::Less => {
-                    *neighbor_distance = distance + cost;
-                    *neighbor_sources = vec![current.clone()];
-                    queue.push((Reverse(*neighbor_distance), neighbor.clone()));
-                }
-                Ordering::Equal => {
-                    neighbor_sources.push(current.clone());
-                }
-                Ordering::Greater => {},
+        stats.peak_queue_len = stats.peak_queue_len.max(queue.len());
+    }
+
+    if !queue.is_empty() {
+        let settled: HashSet<&SearchNode> = settled.iter().collect();
+        distances.retain(|node, _| settled.contains(node));
+    }
+
+    break_zero_cost_cycles(&mut distances, settled.iter().cloned());
+
+    let mut nearest_sources: HashMap<SearchNode, SearchNode> = HashMap::new();
+
+    for node in settled {
+        let source = distances
+            .get(&node)
+            .and_then(|(_, predecessors)| predecessors.iter().find_map(|predecessor| nearest_sources.get(predecessor)))
+            .unwrap_or(&node)
+            .clone();
+
+        nearest_sources.insert(node, source);
+    }
+
+    stats.elapsed = start.elapsed();
+
+    SearchResult {
+        value: DistanceMap {
+            distances,
+            nearest_sources,
+            settled_targets,
+        },
+        status,
+        stats,
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BidirectionalPaths<SearchNode, C> where SearchNode: Hash + Eq {
+    pub distance: C,
+    pub meeting_point: SearchNode,
+    pub path: Vec<SearchNode>,
+    pub path_map: PathMap<SearchNode>,
+}
+
+struct Frontier<SearchNode, C> where SearchNode: Hash + Eq {
+    distances: Distances<SearchNode, C>,
+    queue: BinaryHeap<(Reverse<C>, SearchNode)>,
+}
+
+impl<SearchNode, C> Frontier<SearchNode, C> where
+    SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    C: Cost,
+{
+    fn new(start: SearchNode) -> Frontier<SearchNode, C> {
+        Frontier {
+            distances: HashMap::from([(start.clone(), (C::default(), vec![]))]),
+            queue: BinaryHeap::from([(Reverse(C::default()), start)]),
+        }
+    }
+
+    fn peek(&mut self) -> Option<C> {
+        while let Some((Reverse(distance), node)) = self.queue.peek() {
+            if self.distances.get(node).is_some_and(|(best, _)| distance > best) {
+                self.queue.pop();
+            } else {
+                return Some(*distance);
             }
         }
+
+        None
     }
 
-    (distances, targets_reached)
+    fn expand<Neighbors>(&mut self, neighbors: impl Fn(&SearchNode) -> Neighbors) -> Vec<SearchNode> where
+        Neighbors: IntoIterator<Item = (SearchNode, C)>,
+    {
+        let Some((Reverse(distance), current)) = self.peek().and_then(|_| self.queue.pop()) else {
+            return vec![];
+        };
+
+        let mut touched = vec![];
+
+        for (neighbor, cost) in neighbors(&current) {
+            if relax(&mut self.distances, &current, neighbor.clone(), distance, cost) {
+                self.queue.push((Reverse(distance + cost), neighbor.clone()));
+                touched.push(neighbor);
+            }
+        }
+
+        touched
+    }
+
+    fn distance(&self, node: &SearchNode) -> Option<C> {
+        self.distances.get(node).map(|(distance, _)| *distance)
+    }
+
+    fn chain(&self, from: &SearchNode) -> Vec<SearchNode> {
+        let mut chain = vec![from.clone()];
+
+        while let Some(previous) = self.distances.get(chain.last().unwrap()).and_then(|(_, previous)| previous.iter().min()) {
+            chain.push(previous.clone());
+        }
+
+        chain
+    }
+}
+
+pub fn bidirectional_shortest_paths<SearchNode, C, ForwardNeighbors, BackwardNeighbors> (
+    source: SearchNode,
+    target: SearchNode,
+    forward_neighbors: impl Fn(&SearchNode) -> ForwardNeighbors,
+    backward_neighbors: impl Fn(&SearchNode) -> BackwardNeighbors,
+) -> Option<BidirectionalPaths<SearchNode, C>> where
+    SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    C: Cost,
+    ForwardNeighbors: IntoIterator<Item = (SearchNode, C)>,
+    BackwardNeighbors: IntoIterator<Item = (SearchNode, C)>,
+{
+    bidirectional_shortest_paths_with_options(
+        source,
+        target,
+        forward_neighbors,
+        backward_neighbors,
+        &SearchOptions::default(),
+    ).value
+}
+
+pub fn bidirectional_shortest_paths_with_options<SearchNode, C, ForwardNeighbors, BackwardNeighbors> (
+    source: SearchNode,
+    target: SearchNode,
+    forward_neighbors: impl Fn(&SearchNode) -> ForwardNeighbors,
+    backward_neighbors: impl Fn(&SearchNode) -> BackwardNeighbors,
+    options: &SearchOptions<C>,
+) -> SearchResult<Option<BidirectionalPaths<SearchNode, C>>> where
+    SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    C: Cost,
+    ForwardNeighbors: IntoIterator<Item = (SearchNode, C)>,
+    BackwardNeighbors: IntoIterator<Item = (SearchNode, C)>,
+{
+    let start = Instant::now();
+    let mut forward = Frontier::new(source.clone());
+    let mut backward = Frontier::new(target.clone());
+    let mut best: Option<(C, SearchNode)> = (source == target).then(|| (C::default(), source.clone()));
+    let mut status = SearchStatus::Finished;
+    let mut stats = SearchStats { peak_queue_len: 2, ..SearchStats::default() };
+
+    while let (Some(forward_top), Some(backward_top)) = (forward.peek(), backward.peek()) {
+        if best.as_ref().is_some_and(|(best_distance, _)| forward_top + backward_top > *best_distance) {
+            break;
+        }
+
+        if let Some(limit) = options.limit_reached(forward_top + backward_top, &stats) {
+            status = limit;
+            break;
+        }
+
+        stats.nodes_expanded += 1;
+
+        let touched = if forward.queue.len() <= backward.queue.len() {
+            forward.expand(&forward_neighbors)
+        } else {
+            backward.expand(&backward_neighbors)
+        };
+
+        for node in touched {
+            let (Some(forward_distance), Some(backward_distance)) = (forward.distance(&node), backward.distance(&node)) else {
+                continue;
+            };
+
+            let candidate = (forward_distance + backward_distance, node);
+
+            if best.as_ref().is_none_or(|best| candidate < *best) {
+                best = Some(candidate);
+            }
+        }
+
+        stats.peak_queue_len = stats.peak_queue_len.max(forward.queue.len() + backward.queue.len());
+    }
+
+    break_zero_cost_cycles(&mut forward.distances, [source.clone()]);
+    break_zero_cost_cycles(&mut backward.distances, [target.clone()]);
+    stats.elapsed = start.elapsed();
+
+    let value = best
+        .filter(|_| status == SearchStatus::Finished)
+        .map(|(distance, meeting_point)| resolve_bidirectional_paths(&forward, &backward, &source, &target, distance, meeting_point));
+
+    SearchResult { value, status, stats }
+}
+
+fn resolve_bidirectional_paths<SearchNode, C>(
+    forward: &Frontier<SearchNode, C>,
+    backward: &Frontier<SearchNode, C>,
+    source: &SearchNode,
+    target: &SearchNode,
+    distance: C,
+    meeting_point: SearchNode,
+) -> BidirectionalPaths<SearchNode, C> where
+    SearchNode: Clone + PartialEq + PartialOrd + Ord + Hash,
+    C: Cost,
+{
+    let meeting_points = forward
+        .distances
+        .iter()
+        .filter_map(|(node, (forward_distance, _))| Some((node, *forward_distance + backward.distance(node)?)))
+        .filter(|&(_, total)| total == distance)
+        .map(|(node, _)| node.clone())
+        .collect_vec();
+
+    let mut path_map = resolve_path_map(&forward.distances, &meeting_points);
+    let mut backward_path_map: PathMap<SearchNode> = HashMap::new();
+
+    for (node, previous) in resolve_path_map(&backward.distances, &meeting_points) {
+        for previous in previous {
+            backward_path_map.entry(previous).or_default().push(node.clone());
+        }
+    }
+
+    for (node, successors) in backward_path_map {
+        let existing = path_map.entry(node).or_default();
+
+        for (successor, count) in successors.into_iter().counts() {
+            let present = existing.iter().filter(|&existing| *existing == successor).count();
+            existing.extend(iter::repeat_n(successor, count.saturating_sub(present)));
+        }
+    }
+
+    path_map.entry(target.clone()).or_default();
+
+    for (from, to) in back_edges(&path_map, [source.clone()]) {
+        path_map.get_mut(&from).unwrap().retain(|successor| *successor != to);
+    }
+
+    let mut predecessors: PathMap<SearchNode> = HashMap::new();
+
+    for (node, successors) in &path_map {
+        for successor in successors {
+            predecessors.entry(successor.clone()).or_default().push(node.clone());
+        }
+    }
+
+    let mut reaching_target: HashSet<SearchNode> = HashSet::from_iter([target.clone()]);
+    let mut queue = VecDeque::from([target.clone()]);
+
+    while let Some(current) = queue.pop_front() {
+        for predecessor in successors(&predecessors, &current) {
+            if reaching_target.insert(predecessor.clone()) {
+                queue.push_back(predecessor.clone());
+            }
+        }
+    }
+
+    path_map.retain(|node, _| reaching_target.contains(node));
+    path_map.values_mut().for_each(|successors| successors.retain(|successor| reaching_target.contains(successor)));
+
+    let mut path = forward.chain(&meeting_point);
+    path.reverse();
+    path.extend(backward.chain(&meeting_point).into_iter().skip(1));
+
+    BidirectionalPaths {
+        distance,
+        meeting_point,
+        path,
+        path_map,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::random::xorshift;
+
+    fn zero_cost_diamond(node: &char) -> Vec<(char, usize)> {
+        match node {
+            'S' => vec![('A', 1), ('B', 1)],
+            'A' => vec![('B', 0)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn zero_one_bfs_keeps_zero_cost_ties() {
+        let (distance, path_map) = zero_one_bfs('S', zero_cost_diamond, |&node| node == 'B').unwrap();
+
+        assert_eq!(1, distance);
+        assert_eq!(2, count_shortest_paths(&'S', &path_map));
+        assert_eq!(
+            vec![vec!['S', 'A', 'B'], vec!['S', 'B']],
+            all_shortest_paths(&'S', &path_map).sorted().collect_vec(),
+        );
+    }
+
+    #[test]
+    fn dijkstra_keeps_zero_weight_ties() {
+        let neighbors = |node: &char| {
+            zero_cost_diamond(node).into_iter().map(|(neighbor, cost)| (neighbor, cost as u64))
+        };
+
+        let (distance, path_map) = shortest_paths_to_target('S', neighbors, |&node| node == 'B').unwrap();
+
+        assert_eq!(1, distance);
+        assert_eq!(2, count_shortest_paths(&'S', &path_map));
+    }
+
+    #[test]
+    fn a_star_keeps_zero_weight_ties_with_float_costs() {
+        let neighbors = |node: &char| {
+            zero_cost_diamond(node).into_iter().map(|(neighbor, cost)| (neighbor, OrderedFloat(cost as f64 / 2.0)))
+        };
+
+        let (result, _) = shortest_paths_to_target_with_heuristic(
+            'S',
+            neighbors,
+            |&node| node == 'B',
+            |_| OrderedFloat(0.0),
+        );
+
+        let (distance, path_map) = result.unwrap();
+
+        assert_eq!(OrderedFloat(0.5), distance);
+        assert_eq!(2, count_shortest_paths(&'S', &path_map));
+    }
+
+    #[test]
+    fn zero_cost_cycles_do_not_create_cyclic_path_maps() {
+        let neighbors = |node: &char| match node {
+            'S' => vec![('A', 1)],
+            'A' => vec![('B', 0)],
+            'B' => vec![('A', 0), ('T', 1)],
+            _ => vec![],
+        };
+
+        let (distance, path_map) = zero_one_bfs('S', neighbors, |&node| node == 'T').unwrap();
+
+        assert_eq!(2, distance);
+        assert_eq!(1, count_shortest_paths(&'S', &path_map));
+        assert_eq!(Some(vec!['S', 'A', 'B', 'T']), shortest_path(&'S', &path_map));
+    }
+
+    #[test]
+    fn large_zero_cost_plateau() {
+        let size = 200;
+        let neighbors = |&(i, j): &(i32, i32)| {
+            [(i - 1, j), (i + 1, j), (i, j - 1), (i, j + 1)]
+                .into_iter()
+                .filter(|&(i, j)| (0..size).contains(&i) && (0..size).contains(&j))
+                .map(|neighbor| (neighbor, 0usize))
+                .collect_vec()
+        };
+        let target = (size - 1, size - 1);
+
+        let (distance, path_map) = zero_one_bfs((0, 0), neighbors, |&node| node == target).unwrap();
+        assert_eq!(0, distance);
+        assert_eq!(Some(&target), shortest_path(&(0, 0), &path_map).unwrap().last());
+
+        let (distance, path_map) = shortest_paths_to_target((0, 0), neighbors, |&node| node == target).unwrap();
+        assert_eq!(0, distance);
+        assert_eq!(Some(&target), shortest_path(&(0, 0), &path_map).unwrap().last());
+
+        let paths = bidirectional_shortest_paths((0, 0), target, neighbors, neighbors).unwrap();
+        assert_eq!(0, paths.distance);
+        assert_eq!(Some(&target), paths.path.last());
+    }
+
+    #[test]
+    fn bidirectional_keeps_zero_weight_ties() {
+        let backward = |node: &char| match node {
+            'A' => vec![('S', 1u64)],
+            'B' => vec![('S', 1), ('A', 0)],
+            _ => vec![],
+        };
+
+        let paths = bidirectional_shortest_paths(
+            'S',
+            'B',
+            |node: &char| zero_cost_diamond(node).into_iter().map(|(neighbor, cost)| (neighbor, cost as u64)),
+            backward,
+        ).unwrap();
+
+        assert_eq!(1, paths.distance);
+        assert_eq!(2, count_shortest_paths(&'S', &paths.path_map));
+    }
+
+    #[test]
+    fn bidirectional_zero_cost_cycles_stay_acyclic() {
+        let forward = |node: &char| match node {
+            'S' => vec![('A', 0u64)],
+            'A' => vec![('S', 0), ('T', 1)],
+            _ => vec![],
+        };
+        let backward = |node: &char| match node {
+            'S' => vec![('A', 0u64)],
+            'A' => vec![('S', 0)],
+            'T' => vec![('A', 1)],
+            _ => vec![],
+        };
+
+        for (source, target) in [('S', 'T'), ('S', 'S'), ('A', 'S')] {
+            let paths = bidirectional_shortest_paths(source, target, forward, backward).unwrap();
+
+            assert!(all_shortest_paths(&source, &paths.path_map).all(|path| path.last() == Some(&target)));
+            assert_eq!(1, count_shortest_paths(&source, &paths.path_map));
+        }
+    }
+
+    fn line(node: &i32) -> Vec<(i32, usize)> {
+        vec![(node - 1, 1), (node + 1, 1)]
+    }
+
+    #[test]
+    fn deque_searches_respect_options() {
+        let result = bfs_distance_with_options(0, line, |&node| node == 10, &SearchOptions::new().with_max_expanded_nodes(5));
+
+        assert_eq!(SearchStatus::MaxExpandedNodesReached, result.status);
+        assert_eq!(5, result.stats.nodes_expanded);
+        assert!(result.value.is_none());
+
+        let result = zero_one_bfs_with_options(0, line, |&node| node == 10, &SearchOptions::new().with_max_cost(9));
+
+        assert_eq!(SearchStatus::MaxCostReached, result.status);
+        assert!(result.value.is_none());
+
+        let result = zero_one_bfs_with_options(0, line, |&node| node == 10, &SearchOptions::new().with_max_cost(10));
+
+        assert!(result.is_finished());
+        assert_eq!(Some(10), result.value.map(|(distance, _)| distance));
+    }
+
+    #[test]
+    fn multi_source_search_respects_options() {
+        let cancelled = AtomicBool::new(true);
+        let options = SearchOptions::new().with_cancellation(&cancelled);
+        let result = multi_source_distances_with_options([(0, None)], line, |_| false, None, &options);
+
+        assert_eq!(SearchStatus::Cancelled, result.status);
+        assert_eq!(0, result.value.nodes().count());
+
+        let options = SearchOptions::new().with_max_cost(3);
+        let result = multi_source_distances_with_options([(0, None), (20, Some(2))], line, |_| false, None, &options);
+
+        assert_eq!(SearchStatus::MaxCostReached, result.status);
+        assert_eq!(7 + 3, result.value.nodes().count());
+        assert_eq!(Some(3), result.value.distance(&21));
+        assert_eq!(None, result.value.distance(&17));
+    }
+
+    #[test]
+    fn bidirectional_search_respects_options() {
+        let reached = |options: &SearchOptions<usize>| {
+            bidirectional_shortest_paths_with_options(0, 10, line, line, options)
+        };
+
+        let result = reached(&SearchOptions::new().with_max_cost(9));
+
+        assert_eq!(SearchStatus::MaxCostReached, result.status);
+        assert!(result.value.is_none());
+
+        let result = reached(&SearchOptions::new());
+
+        assert!(result.is_finished());
+        assert!(result.stats.nodes_expanded > 0);
+        assert_eq!(Some(10), result.value.map(|paths| paths.distance));
+    }
+
+    #[test]
+    fn multi_source_distances_pick_the_nearest_source() {
+        let distance_map = multi_source_distances([(0, None), (10, Some(3))], line, |&node| node % 4 == 2, Some(3));
+
+        assert_eq!(vec![2, 10, 6], distance_map.settled_targets());
+        assert_eq!(Some(2), distance_map.distance(&2));
+        assert_eq!(Some(4), distance_map.distance(&9));
+        assert_eq!(Some(&0), distance_map.nearest_source(&3));
+        assert_eq!(Some(&10), distance_map.nearest_source(&8));
+        assert_eq!(None, distance_map.distance(&20));
+
+        let path_map = distance_map.path_map(&[6]);
+        assert_eq!(Some(vec![0, 1, 2, 3, 4, 5, 6]), shortest_path(&0, &path_map));
+    }
+
+    #[test]
+    fn multi_source_distances_match_single_source_searches() {
+        let mut random = xorshift(3);
+
+        for _ in 0..200 {
+            let len = random() % 12 + 1;
+            let adjacency = (0..len)
+                .map(|_| (0..random() % 4).map(|_| (random() % len, random() % 5)).collect_vec())
+                .collect_vec();
+            let neighbors = |node: &u64| adjacency[*node as usize].clone();
+            let sources = (0..random() % 3 + 1).map(|_| (random() % len, random() % 4)).collect_vec();
+
+            let distance_map = multi_source_distances(
+                sources.iter().map(|&(source, cost)| (source, Some(cost))),
+                neighbors,
+                |_| false,
+                None,
+            );
+
+            for node in 0..len {
+                let expected = sources
+                    .iter()
+                    .filter_map(|&(source, cost)| Some(cost + distance_to_target(source, neighbors, |&other| other == node)?))
+                    .min();
+
+                assert_eq!(expected, distance_map.distance(&node));
+
+                if let Some(&source) = distance_map.nearest_source(&node) {
+                    let via_source = sources
+                        .iter()
+                        .filter(|&&(other, _)| other == source)
+                        .filter_map(|&(_, cost)| Some(cost + distance_to_target(source, neighbors, |&other| other == node)?))
+                        .min();
+
+                    assert_eq!(expected, via_source);
+                }
+            }
+        }
+    }
 }
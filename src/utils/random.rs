@@ -0,0 +1,12 @@
+#![cfg(test)]
+
+pub fn xorshift(seed: u64) -> impl FnMut() -> u64 {
+    let mut seed = seed;
+
+    move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed
+    }
+}
@@ -0,0 +1,232 @@
+#![allow(dead_code)]
+
+use std::cell::Cell;
+use std::collections::hash_map;
+use std::collections::HashMap;
+
+use super::grid::{Grid, Position};
+
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+    store: HashMap<Position, T>,
+    bounds: Cell<Option<(Position, Position)>>,
+    stale_bounds: Cell<bool>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            store: HashMap::new(),
+            bounds: Cell::new(None),
+            stale_bounds: Cell::new(false),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.store.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.store.is_empty()
+    }
+
+    pub fn bounds(&self) -> Option<(Position, Position)> {
+        if self.stale_bounds.replace(false) {
+            self.bounds.set(self.store.keys().fold(None, expand_bounds));
+        }
+
+        self.bounds.get()
+    }
+
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.rows(), self.cols())
+    }
+
+    pub fn rows(&self) -> usize {
+        self.bounds()
+            .map(|(Position(i_min, _), Position(i_max, _))| (i_max - i_min + 1) as usize)
+            .unwrap_or_default()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.bounds()
+            .map(|(Position(_, j_min), Position(_, j_max))| (j_max - j_min + 1) as usize)
+            .unwrap_or_default()
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        self.store.contains_key(position)
+    }
+
+    pub fn get(&self, position: &Position) -> Option<&T> {
+        self.store.get(position)
+    }
+
+    pub fn get_mut(&mut self, position: &Position) -> Option<&mut T> {
+        self.store.get_mut(position)
+    }
+
+    pub fn set(&mut self, position: &Position, value: T) {
+        if !self.stale_bounds.get() {
+            self.bounds.set(expand_bounds(self.bounds.get(), position));
+        }
+
+        self.store.insert(*position, value);
+    }
+
+    /// Removing a cell on the current bounding box only marks the bounds as stale. They are
+    /// recomputed from the remaining positions the next time they are needed.
+    pub fn remove(&mut self, position: &Position) -> Option<T> {
+        let value = self.store.remove(position)?;
+
+        if let Some((Position(i_min, j_min), Position(i_max, j_max))) = self.bounds.get() {
+            let &Position(i, j) = position;
+
+            if i == i_min || i == i_max || j == j_min || j == j_max {
+                self.stale_bounds.set(true);
+            }
+        }
+
+        Some(value)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = &Position> {
+        self.store.keys()
+    }
+
+    pub fn iter(&self) -> hash_map::Iter<'_, Position, T> {
+        self.store.iter()
+    }
+
+}
+
+fn expand_bounds(bounds: Option<(Position, Position)>, &Position(i, j): &Position) -> Option<(Position, Position)> {
+    Some(match bounds {
+        None => (Position(i, j), Position(i, j)),
+        Some((Position(i_min, j_min), Position(i_max, j_max))) => (
+            Position(i_min.min(i), j_min.min(j)),
+            Position(i_max.max(i), j_max.max(j)),
+        ),
+    })
+}
+
+impl<T> FromIterator<(Position, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Position, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+
+        for (position, value) in iter {
+            grid.set(&position, value);
+        }
+
+        grid
+    }
+}
+
+impl<T> IntoIterator for SparseGrid<T> {
+    type Item = (Position, T);
+    type IntoIter = hash_map::IntoIter<Position, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.store.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SparseGrid<T> {
+    type Item = (&'a Position, &'a T);
+    type IntoIter = hash_map::Iter<'a, Position, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.store.iter()
+    }
+}
+
+impl<T> From<Grid<T>> for SparseGrid<T> where T: Copy {
+    fn from(grid: Grid<T>) -> Self {
+        grid.into_iter().collect()
+    }
+}
+
+impl<T> SparseGrid<T> where T: Clone {
+    pub fn to_grid(&self, empty: T) -> (Grid<T>, Position) {
+        let Some((origin, _)) = self.bounds() else {
+            return (Grid::new_with_value(0usize, 0usize, empty), Position(0, 0));
+        };
+
+        let mut grid = Grid::new_with_value(self.rows(), self.cols(), empty);
+
+        for (&Position(i, j), value) in &self.store {
+            grid.set(&Position(i - origin.0, j - origin.1), value.clone());
+        }
+
+        (grid, origin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_follow_set_and_remove() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(None, grid.bounds());
+        assert_eq!((0, 0), grid.dimensions());
+
+        grid.set(&Position(2, -1), 'a');
+        grid.set(&Position(-3, 4), 'b');
+        grid.set(&Position(0, 0), 'c');
+        assert_eq!(Some((Position(-3, -1), Position(2, 4))), grid.bounds());
+        assert_eq!((6, 6), grid.dimensions());
+
+        assert_eq!(None, grid.remove(&Position(5, 5)));
+        assert_eq!(Some('c'), grid.remove(&Position(0, 0)));
+        assert_eq!(Some((Position(-3, -1), Position(2, 4))), grid.bounds());
+
+        assert_eq!(Some('b'), grid.remove(&Position(-3, 4)));
+        grid.set(&Position(1, 1), 'd');
+        assert_eq!(Some((Position(1, -1), Position(2, 1))), grid.bounds());
+        assert_eq!((2, 3), grid.dimensions());
+
+        grid.remove(&Position(1, 1));
+        grid.remove(&Position(2, -1));
+        assert!(grid.is_empty());
+        assert_eq!(None, grid.bounds());
+        assert_eq!(0, grid.rows());
+    }
+
+    #[test]
+    fn clearing_edge_by_edge_shrinks_bounds() {
+        let mut grid: SparseGrid<usize> = (0..50).map(|i| (Position(i, i), i as usize)).collect();
+
+        for i in 0..49 {
+            assert_eq!(Some(i as usize), grid.remove(&Position(i, i)));
+            assert_eq!(Some((Position(i + 1, i + 1), Position(49, 49))), grid.bounds());
+        }
+    }
+
+    #[test]
+    fn converts_to_and_from_grid() {
+        let mut dense = Grid::new_with_value(2usize, 3usize, 0u8);
+        dense.set(&Position(1, 2), 7);
+
+        let sparse = SparseGrid::from(dense);
+        assert_eq!(6, sparse.len());
+        assert_eq!(Some(&7), sparse.get(&Position(1, 2)));
+        assert_eq!(Some((Position(0, 0), Position(1, 2))), sparse.bounds());
+
+        let sparse: SparseGrid<char> = [(Position(-1, 3), '#'), (Position(1, 4), '#')].into_iter().collect();
+        let (grid, origin) = sparse.to_grid('.');
+        assert_eq!(Position(-1, 3), origin);
+        assert_eq!("#.\n..\n.#", grid.to_string());
+
+        let (grid, origin) = SparseGrid::new().to_grid('.');
+        assert_eq!((0, 0), grid.dimensions::<usize>());
+        assert_eq!(Position(0, 0), origin);
+    }
+}
@@ -0,0 +1,197 @@
+#![allow(dead_code)]
+
+use std::cmp::{max, min};
+use std::ops::{Add, Sub};
+
+use super::grid::{Grid, Position};
+
+pub struct SummedAreaTable<T> where T: Clone {
+    table: Grid<T>,
+}
+
+impl<T> SummedAreaTable<T> where T: Copy + Default + Add<Output = T> + Sub<Output = T> {
+    pub fn new<U>(grid: &Grid<U>, value: impl Fn(&U) -> T) -> SummedAreaTable<T> where U: Clone {
+        let (rows, cols) = grid.dimensions::<usize>();
+        let mut table = Grid::new(rows + 1, cols + 1);
+
+        for (Position(i, j), cell) in grid {
+            let sum = value(cell) + table[(i, j + 1)] + table[(i + 1, j)] - table[(i, j)];
+            table[(i + 1, j + 1)] = sum;
+        }
+
+        SummedAreaTable { table }
+    }
+
+    pub fn sum(&self, p1: &Position, p2: &Position) -> T {
+        let (Position(i_min, j_min), Position(i_max, j_max)) = normalize(p1, p2);
+
+        if i_min < 0 || j_min < 0 || i_max + 1 >= self.table.rows() || j_max + 1 >= self.table.cols() {
+            panic!("Rectangle out of bounds: ({i_min}, {j_min}) - ({i_max}, {j_max})");
+        }
+
+        self.table[(i_max + 1, j_max + 1)] + self.table[(i_min, j_min)]
+            - self.table[(i_min, j_max + 1)] - self.table[(i_max + 1, j_min)]
+    }
+}
+
+impl<T> Grid<T> where T: Copy + Default + Add<Output = T> + Sub<Output = T> {
+    pub fn summed_area_table(&self) -> SummedAreaTable<T> {
+        SummedAreaTable::new(self, |value| *value)
+    }
+}
+
+pub struct FilledAreaTable {
+    counts: SummedAreaTable<usize>,
+}
+
+impl FilledAreaTable {
+    pub fn count(&self, p1: &Position, p2: &Position) -> usize {
+        self.counts.sum(p1, p2)
+    }
+
+    pub fn is_filled(&self, p1: &Position, p2: &Position) -> bool {
+        self.count(p1, p2) == area(p1, p2)
+    }
+
+    pub fn is_empty(&self, p1: &Position, p2: &Position) -> bool {
+        self.count(p1, p2) == 0
+    }
+}
+
+impl Grid<bool> {
+    pub fn filled_area_table(&self) -> FilledAreaTable {
+        FilledAreaTable {
+            counts: SummedAreaTable::new(self, |&filled| filled as usize),
+        }
+    }
+
+    pub fn largest_filled_rectangle(&self) -> Option<(Position, Position)> {
+        let (rows, cols) = self.dimensions::<usize>();
+        let mut heights = vec![0; cols];
+        let mut best: Option<(usize, Position, Position)> = None;
+
+        for i in 0..rows {
+            for (j, height) in heights.iter_mut().enumerate() {
+                *height = if self[(i, j)] { *height + 1 } else { 0 };
+            }
+
+            let mut stack: Vec<(usize, usize)> = vec![];
+
+            for j in 0..=cols {
+                let height = heights.get(j).copied().unwrap_or(0);
+                let mut start = j;
+
+                while let Some(&(stack_start, stack_height)) = stack.last() && stack_height >= height {
+                    stack.pop();
+
+                    let area = stack_height * (j - stack_start);
+
+                    if area > 0 && best.is_none_or(|(best_area, _, _)| area > best_area) {
+                        best = Some((
+                            area,
+                            Position::new(i + 1 - stack_height, stack_start),
+                            Position::new(i, j - 1),
+                        ));
+                    }
+
+                    start = stack_start;
+                }
+
+                stack.push((start, height));
+            }
+        }
+
+        best.map(|(_, top_left, bottom_right)| (top_left, bottom_right))
+    }
+}
+
+fn normalize(&Position(i1, j1): &Position, &Position(i2, j2): &Position) -> (Position, Position) {
+    (Position(min(i1, i2), min(j1, j2)), Position(max(i1, i2), max(j1, j2)))
+}
+
+pub fn area(p1: &Position, p2: &Position) -> usize {
+    let (Position(i_min, j_min), Position(i_max, j_max)) = normalize(p1, p2);
+    ((i_max - i_min + 1) * (j_max - j_min + 1)) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::utils::random::xorshift;
+
+    fn random_grids() -> impl Iterator<Item = Grid<bool>> {
+        let mut random = xorshift(19);
+
+        (0..300).map(move |_| {
+            let (rows, cols) = ((random() % 7 + 1) as usize, (random() % 7 + 1) as usize);
+            let mut grid = Grid::new(rows, cols);
+
+            for (i, j) in (0..rows).cartesian_product(0..cols) {
+                grid.set(&Position::new(i, j), !random().is_multiple_of(4));
+            }
+
+            grid
+        })
+    }
+
+    fn rectangles(grid: &Grid<bool>) -> impl Iterator<Item = (Position, Position)> + use<> {
+        let (rows, cols) = grid.dimensions::<isize>();
+
+        (0..rows)
+            .cartesian_product(0..cols)
+            .cartesian_product((0..rows).cartesian_product(0..cols))
+            .filter(|&((i1, j1), (i2, j2))| i1 <= i2 && j1 <= j2)
+            .map(|((i1, j1), (i2, j2))| (Position(i1, j1), Position(i2, j2)))
+    }
+
+    fn cells(p1: &Position, p2: &Position) -> impl Iterator<Item = (isize, isize)> + use<> {
+        (p1.0..=p2.0).cartesian_product(p1.1..=p2.1)
+    }
+
+    #[test]
+    fn sums_match_brute_force() {
+        let mut grid: Grid<i64> = Grid::new(3usize, 3usize);
+
+        for (index, (i, j)) in (0..3).cartesian_product(0..3).enumerate() {
+            grid.set(&Position(i, j), index as i64 + 1);
+        }
+
+        let table = grid.summed_area_table();
+
+        assert_eq!(45, table.sum(&Position(0, 0), &Position(2, 2)));
+        assert_eq!(28, table.sum(&Position(2, 2), &Position(1, 1)));
+        assert_eq!(5, table.sum(&Position(1, 1), &Position(1, 1)));
+
+        for grid in random_grids().take(50) {
+            let table = grid.filled_area_table();
+
+            for (p1, p2) in rectangles(&grid) {
+                let count = cells(&p1, &p2).filter(|&(i, j)| grid[(i, j)]).count();
+
+                assert_eq!(count, table.count(&p1, &p2));
+                assert_eq!(count == area(&p1, &p2), table.is_filled(&p2, &p1));
+                assert_eq!(count == 0, table.is_empty(&p1, &p2));
+            }
+        }
+    }
+
+    #[test]
+    fn largest_filled_rectangle_matches_brute_force() {
+        for grid in random_grids() {
+            let expected = rectangles(&grid)
+                .filter(|(p1, p2)| cells(p1, p2).all(|(i, j)| grid[(i, j)]))
+                .map(|(p1, p2)| area(&p1, &p2))
+                .max();
+
+            let rectangle = grid.largest_filled_rectangle();
+
+            assert_eq!(expected, rectangle.map(|(p1, p2)| area(&p1, &p2)));
+
+            if let Some((p1, p2)) = rectangle {
+                assert!(cells(&p1, &p2).all(|(i, j)| grid[(i, j)]));
+            }
+        }
+    }
+}
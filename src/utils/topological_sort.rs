@@ -0,0 +1,580 @@
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N> Display for Cycle<N> where N: Debug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nodes = self.0.iter().chain(self.0.first()).map(|node| format!("{node:?}")).join(" -> ");
+        write!(f, "Cycle detected: {nodes}")
+    }
+}
+
+impl<N> Error for Cycle<N> where N: Debug {}
+
+struct IndexedGraph<N> {
+    nodes: Vec<N>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl<N> IndexedGraph<N> where N: Clone + Eq + Hash {
+    fn new<I>(roots: impl IntoIterator<Item = N>, successors: impl Fn(&N) -> I) -> IndexedGraph<N> where
+        I: IntoIterator<Item = N>,
+    {
+        let mut nodes = vec![];
+        let mut indices: HashMap<N, usize> = HashMap::new();
+        let mut adjacency: Vec<Vec<usize>> = vec![];
+
+        let mut index_of = |node: N, nodes: &mut Vec<N>, adjacency: &mut Vec<Vec<usize>>| -> usize {
+            *indices.entry(node.clone()).or_insert_with(|| {
+                nodes.push(node);
+                adjacency.push(vec![]);
+                nodes.len() - 1
+            })
+        };
+
+        for root in roots {
+            index_of(root, &mut nodes, &mut adjacency);
+        }
+
+        let mut current = 0;
+
+        while current < nodes.len() {
+            for successor in successors(&nodes[current]) {
+                let successor = index_of(successor, &mut nodes, &mut adjacency);
+                adjacency[current].push(successor);
+            }
+
+            current += 1;
+        }
+
+        IndexedGraph { nodes, adjacency }
+    }
+
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn resolve(&self, indices: impl IntoIterator<Item = usize>) -> Vec<N> {
+        indices.into_iter().map(|index| self.nodes[index].clone()).collect()
+    }
+
+    fn reversed_adjacency(&self) -> Vec<Vec<usize>> {
+        let mut reversed = vec![vec![]; self.len()];
+
+        for (from, successors) in self.adjacency.iter().enumerate() {
+            for &to in successors {
+                reversed[to].push(from);
+            }
+        }
+
+        reversed
+    }
+
+    fn depth_first_postorder(&self) -> Result<Vec<usize>, Vec<usize>> {
+        const UNVISITED: u8 = 0;
+        const IN_PROGRESS: u8 = 1;
+        const DONE: u8 = 2;
+
+        let mut state = vec![UNVISITED; self.len()];
+        let mut postorder = vec![];
+
+        for root in 0..self.len() {
+            if state[root] != UNVISITED {
+                continue;
+            }
+
+            let mut stack = vec![(root, 0)];
+            state[root] = IN_PROGRESS;
+
+            while let Some((current, next_edge)) = stack.last_mut() {
+                let current = *current;
+
+                if let Some(&successor) = self.adjacency[current].get(*next_edge) {
+                    *next_edge += 1;
+
+                    match state[successor] {
+                        UNVISITED => {
+                            state[successor] = IN_PROGRESS;
+                            stack.push((successor, 0));
+                        },
+                        IN_PROGRESS => {
+                            let start = stack.iter().position(|&(node, _)| node == successor).unwrap();
+                            return Err(stack[start..].iter().map(|&(node, _)| node).collect());
+                        },
+                        _ => {},
+                    }
+                } else {
+                    state[current] = DONE;
+                    postorder.push(current);
+                    stack.pop();
+                }
+            }
+        }
+
+        Ok(postorder)
+    }
+
+    fn find_cycle(&self) -> Option<Vec<usize>> {
+        self.depth_first_postorder().err()
+    }
+
+    fn plain_postorder(adjacency: &[Vec<usize>]) -> Vec<usize> {
+        let mut visited = vec![false; adjacency.len()];
+        let mut postorder = vec![];
+
+        for root in 0..adjacency.len() {
+            if visited[root] {
+                continue;
+            }
+
+            let mut stack = vec![(root, 0)];
+            visited[root] = true;
+
+            while let Some((current, next_edge)) = stack.last_mut() {
+                let current = *current;
+
+                if let Some(&successor) = adjacency[current].get(*next_edge) {
+                    *next_edge += 1;
+
+                    if !visited[successor] {
+                        visited[successor] = true;
+                        stack.push((successor, 0));
+                    }
+                } else {
+                    postorder.push(current);
+                    stack.pop();
+                }
+            }
+        }
+
+        postorder
+    }
+
+    fn tarjan(&self) -> Vec<Vec<usize>> {
+        let mut index = vec![usize::MAX; self.len()];
+        let mut low_link = vec![0; self.len()];
+        let mut on_stack = vec![false; self.len()];
+        let mut component_stack = vec![];
+        let mut components = vec![];
+        let mut next_index = 0;
+
+        for root in 0..self.len() {
+            if index[root] != usize::MAX {
+                continue;
+            }
+
+            let mut call_stack = vec![(root, 0)];
+            index[root] = next_index;
+            low_link[root] = next_index;
+            next_index += 1;
+            component_stack.push(root);
+            on_stack[root] = true;
+
+            while let Some((current, next_edge)) = call_stack.last_mut() {
+                let current = *current;
+
+                if let Some(&successor) = self.adjacency[current].get(*next_edge) {
+                    *next_edge += 1;
+
+                    if index[successor] == usize::MAX {
+                        index[successor] = next_index;
+                        low_link[successor] = next_index;
+                        next_index += 1;
+                        component_stack.push(successor);
+                        on_stack[successor] = true;
+                        call_stack.push((successor, 0));
+                    } else if on_stack[successor] {
+                        low_link[current] = low_link[current].min(index[successor]);
+                    }
+                } else {
+                    call_stack.pop();
+
+                    if let Some(&(parent, _)) = call_stack.last() {
+                        low_link[parent] = low_link[parent].min(low_link[current]);
+                    }
+
+                    if low_link[current] == index[current] {
+                        let mut component = vec![];
+
+                        while let Some(node) = component_stack.pop() {
+                            on_stack[node] = false;
+                            component.push(node);
+
+                            if node == current {
+                                break;
+                            }
+                        }
+
+                        components.push(component);
+                    }
+                }
+            }
+        }
+
+        components.reverse();
+        components
+    }
+
+    fn kosaraju(&self) -> Vec<Vec<usize>> {
+        let reversed = self.reversed_adjacency();
+        let mut component_of = vec![usize::MAX; self.len()];
+        let mut components = vec![];
+
+        for root in IndexedGraph::<N>::plain_postorder(&self.adjacency).into_iter().rev() {
+            if component_of[root] != usize::MAX {
+                continue;
+            }
+
+            let mut component = vec![root];
+            let mut stack = vec![root];
+            component_of[root] = components.len();
+
+            while let Some(current) = stack.pop() {
+                for &predecessor in &reversed[current] {
+                    if component_of[predecessor] == usize::MAX {
+                        component_of[predecessor] = components.len();
+                        component.push(predecessor);
+                        stack.push(predecessor);
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        components
+    }
+}
+
+pub fn kahn_topological_sort<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    successors: impl Fn(&N) -> I,
+) -> Result<Vec<N>, Cycle<N>> where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let graph = IndexedGraph::new(nodes, successors);
+    let mut in_degrees = vec![0; graph.len()];
+
+    for &successor in graph.adjacency.iter().flatten() {
+        in_degrees[successor] += 1;
+    }
+
+    let mut queue: VecDeque<usize> = (0..graph.len()).filter(|&node| in_degrees[node] == 0).collect();
+    let mut order = vec![];
+
+    while let Some(current) = queue.pop_front() {
+        order.push(current);
+
+        for &successor in &graph.adjacency[current] {
+            in_degrees[successor] -= 1;
+
+            if in_degrees[successor] == 0 {
+                queue.push_back(successor);
+            }
+        }
+    }
+
+    if order.len() < graph.len() {
+        let cycle = graph.find_cycle().expect("Kahn's algorithm stalled without a cycle");
+        return Err(Cycle(graph.resolve(cycle)));
+    }
+
+    Ok(graph.resolve(order))
+}
+
+pub fn dfs_topological_sort<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    successors: impl Fn(&N) -> I,
+) -> Result<Vec<N>, Cycle<N>> where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let graph = IndexedGraph::new(nodes, successors);
+
+    match graph.depth_first_postorder() {
+        Ok(postorder) => Ok(graph.resolve(postorder.into_iter().rev())),
+        Err(cycle) => Err(Cycle(graph.resolve(cycle))),
+    }
+}
+
+pub fn find_cycle<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    successors: impl Fn(&N) -> I,
+) -> Option<Cycle<N>> where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let graph = IndexedGraph::new(nodes, successors);
+    graph.find_cycle().map(|cycle| Cycle(graph.resolve(cycle)))
+}
+
+pub fn tarjan_scc<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    successors: impl Fn(&N) -> I,
+) -> Vec<Vec<N>> where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let graph = IndexedGraph::new(nodes, successors);
+
+    graph
+        .tarjan()
+        .into_iter()
+        .map(|component| graph.resolve(component))
+        .collect()
+}
+
+pub fn kosaraju_scc<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    successors: impl Fn(&N) -> I,
+) -> Vec<Vec<N>> where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let graph = IndexedGraph::new(nodes, successors);
+
+    graph
+        .kosaraju()
+        .into_iter()
+        .map(|component| graph.resolve(component))
+        .collect()
+}
+
+#[derive(Clone, Debug)]
+pub struct Condensation<N> where N: Clone + Eq + Hash {
+    components: Vec<Vec<N>>,
+    component_of: HashMap<N, usize>,
+    edges: Vec<Vec<usize>>,
+    cyclic: Vec<bool>,
+}
+
+impl<N> Condensation<N> where N: Clone + Eq + Hash {
+    pub fn new<I>(nodes: impl IntoIterator<Item = N>, successors: impl Fn(&N) -> I) -> Condensation<N> where
+        I: IntoIterator<Item = N>,
+    {
+        let graph = IndexedGraph::new(nodes, successors);
+        let components = graph.tarjan();
+        let mut component_index = vec![0; graph.len()];
+
+        for (index, component) in components.iter().enumerate() {
+            for &node in component {
+                component_index[node] = index;
+            }
+        }
+
+        let mut edges = vec![vec![]; components.len()];
+        let mut cyclic = components.iter().map(|component| component.len() > 1).collect_vec();
+
+        for (from, successors) in graph.adjacency.iter().enumerate() {
+            for &to in successors {
+                if component_index[from] != component_index[to] {
+                    edges[component_index[from]].push(component_index[to]);
+                } else if from == to {
+                    cyclic[component_index[from]] = true;
+                }
+            }
+        }
+
+        for successors in edges.iter_mut() {
+            successors.sort_unstable();
+            successors.dedup();
+        }
+
+        let component_of = graph.nodes.iter().cloned().zip(component_index).collect();
+        let components = components.into_iter().map(|component| graph.resolve(component)).collect();
+
+        Condensation { components, component_of, edges, cyclic }
+    }
+
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    pub fn components(&self) -> &[Vec<N>] {
+        &self.components
+    }
+
+    pub fn component(&self, index: usize) -> &[N] {
+        &self.components[index]
+    }
+
+    pub fn component_of(&self, node: &N) -> Option<usize> {
+        self.component_of.get(node).copied()
+    }
+
+    pub fn successors(&self, index: usize) -> &[usize] {
+        &self.edges[index]
+    }
+
+    pub fn is_cyclic_component(&self, index: usize) -> bool {
+        self.cyclic[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hashbrown::HashSet;
+
+    use super::*;
+    use crate::utils::random::xorshift;
+
+    fn normalized(components: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+        components
+            .into_iter()
+            .map(|component| component.into_iter().sorted().collect_vec())
+            .sorted()
+            .collect()
+    }
+
+    fn random_graphs(seed: u64, acyclic: bool) -> impl Iterator<Item = Vec<Vec<usize>>> {
+        let mut random = xorshift(seed);
+
+        (0..300).map(move |_| {
+            let len = (random() % 10 + 1) as usize;
+            let mut adjacency = vec![vec![]; len];
+
+            for _ in 0..random() % 20 {
+                let (from, to) = ((random() % len as u64) as usize, (random() % len as u64) as usize);
+
+                if !acyclic || from < to {
+                    adjacency[from].push(to);
+                }
+            }
+
+            adjacency
+        })
+    }
+
+    fn is_cycle(adjacency: &[Vec<usize>], cycle: &[usize]) -> bool {
+        !cycle.is_empty() && cycle
+            .iter()
+            .zip(cycle.iter().cycle().skip(1))
+            .all(|(from, to)| adjacency[*from].contains(to))
+    }
+
+    #[test]
+    fn strongly_connected_components() {
+        let successors = |node: &usize| match node {
+            1 => vec![2],
+            2 => vec![3],
+            3 => vec![1, 4],
+            4 => vec![5],
+            5 => vec![4],
+            _ => vec![],
+        };
+
+        let expected = vec![vec![1, 2, 3], vec![4, 5], vec![6]];
+
+        assert_eq!(expected, normalized(tarjan_scc([1, 6], successors)));
+        assert_eq!(expected, normalized(kosaraju_scc([1, 6], successors)));
+    }
+
+    #[test]
+    fn tarjan_matches_kosaraju_and_reachability() {
+        for adjacency in random_graphs(7, false) {
+            let len = adjacency.len();
+            let successors = |node: &usize| adjacency[*node].clone();
+            let reachable = (0..len)
+                .map(|source| {
+                    let mut seen: HashSet<usize> = HashSet::from_iter([source]);
+                    let mut stack = vec![source];
+
+                    while let Some(current) = stack.pop() {
+                        stack.extend(adjacency[current].iter().filter(|&&next| seen.insert(next)));
+                    }
+
+                    seen
+                })
+                .collect_vec();
+
+            let expected = (0..len)
+                .map(|a| (0..len).filter(|&b| reachable[a].contains(&b) && reachable[b].contains(&a)).collect_vec())
+                .unique()
+                .sorted()
+                .collect_vec();
+
+            assert_eq!(expected, normalized(tarjan_scc(0..len, successors)));
+            assert_eq!(expected, normalized(kosaraju_scc(0..len, successors)));
+        }
+    }
+
+    #[test]
+    fn topological_sorts_respect_edges() {
+        for adjacency in random_graphs(11, true) {
+            let len = adjacency.len();
+            let successors = |node: &usize| adjacency[*node].clone();
+
+            for order in [kahn_topological_sort(0..len, successors), dfs_topological_sort(0..len, successors)] {
+                let order = order.unwrap();
+                let position = |node: usize| order.iter().position(|&other| other == node).unwrap();
+
+                assert_eq!(len, order.len());
+                assert!((0..len).all(|from| adjacency[from].iter().all(|&to| position(from) < position(to))));
+            }
+
+            assert_eq!(None, find_cycle(0..len, successors));
+        }
+    }
+
+    #[test]
+    fn cycles_are_reported() {
+        for adjacency in random_graphs(13, false) {
+            let len = adjacency.len();
+            let successors = |node: &usize| adjacency[*node].clone();
+            let has_cycle = tarjan_scc(0..len, successors).iter().any(|component| {
+                component.len() > 1 || adjacency[component[0]].contains(&component[0])
+            });
+
+            let results = [
+                kahn_topological_sort(0..len, successors).err(),
+                dfs_topological_sort(0..len, successors).err(),
+                find_cycle(0..len, successors),
+            ];
+
+            for result in results {
+                assert_eq!(has_cycle, result.is_some());
+
+                if let Some(Cycle(cycle)) = result {
+                    assert!(is_cycle(&adjacency, &cycle), "{cycle:?} is not a cycle in {adjacency:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn condensation() {
+        let successors = |node: &char| match node {
+            'a' => vec!['b'],
+            'b' => vec!['a', 'c'],
+            'c' => vec!['c', 'd'],
+            _ => vec![],
+        };
+
+        let condensation = Condensation::new(['a'], successors);
+        let ab = condensation.component_of(&'a').unwrap();
+        let c = condensation.component_of(&'c').unwrap();
+        let d = condensation.component_of(&'d').unwrap();
+
+        assert_eq!(3, condensation.len());
+        assert_eq!(Some(ab), condensation.component_of(&'b'));
+        assert_eq!(&[c], condensation.successors(ab));
+        assert_eq!(&[d], condensation.successors(c));
+        assert!(condensation.is_cyclic_component(ab));
+        assert!(condensation.is_cyclic_component(c));
+        assert!(!condensation.is_cyclic_component(d));
+    }
+}
@@ -0,0 +1,233 @@
+#![allow(dead_code)]
+
+use itertools::Itertools;
+
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    pub fn new(len: usize) -> UnionFind {
+        UnionFind {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            components: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut current = element;
+
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    pub fn union(&mut self, a: usize, b: usize) -> Option<usize> {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+
+        if a == b {
+            return None;
+        }
+
+        if self.sizes[a] < self.sizes[b] {
+            (a, b) = (b, a);
+        }
+
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.components -= 1;
+
+        Some(a)
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    pub fn component_sizes(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&element| self.parents[element] == element)
+            .map(|root| self.sizes[root])
+            .collect()
+    }
+
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        (0..self.len())
+            .into_group_map_by(|&element| self.find(element))
+            .into_values()
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MergeEvent {
+    pub edge_index: usize,
+    pub a: usize,
+    pub b: usize,
+    pub size: usize,
+    pub components: usize,
+}
+
+pub struct Kruskal<I> {
+    union_find: UnionFind,
+    edges: I,
+    edge_index: usize,
+}
+
+impl<I> Kruskal<I> where I: Iterator<Item = (usize, usize)> {
+    pub fn new(len: usize, edges: impl IntoIterator<IntoIter = I>) -> Kruskal<I> {
+        Kruskal {
+            union_find: UnionFind::new(len),
+            edges: edges.into_iter(),
+            edge_index: 0,
+        }
+    }
+
+    pub fn union_find(&self) -> &UnionFind {
+        &self.union_find
+    }
+
+    pub fn into_union_find(self) -> UnionFind {
+        self.union_find
+    }
+}
+
+impl<I> Iterator for Kruskal<I> where I: Iterator<Item = (usize, usize)> {
+    type Item = MergeEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (a, b) in self.edges.by_ref() {
+            let edge_index = self.edge_index;
+            self.edge_index += 1;
+
+            if let Some(root) = self.union_find.union(a, b) {
+                return Some(MergeEvent {
+                    edge_index,
+                    a,
+                    b,
+                    size: self.union_find.sizes[root],
+                    components: self.union_find.components,
+                });
+            }
+        }
+
+        None
+    }
+}
+
+pub fn minimum_spanning_forest<W>(len: usize, edges: impl IntoIterator<Item = (usize, usize, W)>) -> Vec<(usize, usize, W)> where
+    W: Ord,
+{
+    let mut union_find = UnionFind::new(len);
+
+    edges
+        .into_iter()
+        .sorted_by(|(_, _, w1), (_, _, w2)| w1.cmp(w2))
+        .filter(|&(a, b, _)| union_find.union(a, b).is_some())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::random::xorshift;
+
+    #[test]
+    fn union_merges_components() {
+        let mut union_find = UnionFind::new(6);
+
+        assert!(union_find.union(0, 1).is_some());
+        assert!(union_find.union(2, 3).is_some());
+        assert!(union_find.union(1, 3).is_some());
+        assert_eq!(None, union_find.union(0, 2));
+
+        assert!(union_find.connected(0, 3));
+        assert!(!union_find.connected(0, 4));
+        assert_eq!(4, union_find.size(2));
+        assert_eq!(1, union_find.size(5));
+        assert_eq!(3, union_find.components());
+        assert_eq!(vec![1, 1, 4], union_find.component_sizes().into_iter().sorted().collect_vec());
+        assert_eq!(
+            vec![vec![0, 1, 2, 3], vec![4], vec![5]],
+            union_find.groups().into_iter().sorted().collect_vec(),
+        );
+    }
+
+    #[test]
+    fn kruskal_reports_merges_with_edge_indices() {
+        let edges = [(0, 1), (1, 0), (2, 3), (0, 3), (1, 2), (3, 4)];
+        let mut kruskal = Kruskal::new(5, edges);
+
+        assert_eq!(Some(MergeEvent { edge_index: 0, a: 0, b: 1, size: 2, components: 4 }), kruskal.next());
+        assert_eq!(Some(MergeEvent { edge_index: 2, a: 2, b: 3, size: 2, components: 3 }), kruskal.next());
+        assert_eq!(Some(MergeEvent { edge_index: 3, a: 0, b: 3, size: 4, components: 2 }), kruskal.next());
+        assert_eq!(Some(MergeEvent { edge_index: 5, a: 3, b: 4, size: 5, components: 1 }), kruskal.next());
+        assert_eq!(None, kruskal.next());
+        assert_eq!(1, kruskal.into_union_find().components());
+    }
+
+    #[test]
+    fn minimum_spanning_forest_matches_brute_force() {
+        let mut random = xorshift(29);
+
+        for _ in 0..200 {
+            let len = (random() % 6 + 1) as usize;
+            let edges = (0..random() % 9)
+                .map(|_| ((random() % len as u64) as usize, (random() % len as u64) as usize, random() % 10))
+                .collect_vec();
+
+            let mut all = UnionFind::new(len);
+
+            for &(a, b, _) in &edges {
+                all.union(a, b);
+            }
+
+            let expected = edges
+                .iter()
+                .powerset()
+                .filter(|subset| subset.len() == len - all.components())
+                .filter(|subset| {
+                    let mut union_find = UnionFind::new(len);
+                    subset.iter().all(|&&(a, b, _)| union_find.union(a, b).is_some())
+                })
+                .map(|subset| subset.iter().map(|&&(_, _, weight)| weight).sum::<u64>())
+                .min();
+
+            let forest = minimum_spanning_forest(len, edges.iter().copied());
+
+            assert_eq!(len - all.components(), forest.len());
+            assert_eq!(expected, Some(forest.iter().map(|&(_, _, weight)| weight).sum()));
+        }
+    }
+}